@@ -0,0 +1,36 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Build script: records the git commit and compiler version so benchmark
+// reports can say exactly which build produced them.
+
+use std::process::Command;
+
+fn main() {
+    let output = |program: &str, args: &[&str]| {
+        Command::new(program)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|text| text.trim().to_string())
+            .unwrap_or_default()
+    };
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+
+    // Empty when git or the repository is unavailable, e.g. in a source tarball.
+    println!(
+        "cargo:rustc-env=BUILD_GIT_COMMIT={}",
+        output("git", &["rev-parse", "--short=12", "HEAD"])
+    );
+    println!(
+        "cargo:rustc-env=BUILD_RUSTC_VERSION={}",
+        output(&rustc, &["--version"])
+    );
+    // HEAD's reflog changes on every commit and checkout. Watching a missing
+    // path would make Cargo rerun this script on every build.
+    if std::path::Path::new(".git/logs/HEAD").exists() {
+        println!("cargo:rerun-if-changed=.git/logs/HEAD");
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
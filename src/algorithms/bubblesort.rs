@@ -1,26 +1,33 @@
 // Copyright (c) 2026 Neil Pandya
 
-use crate::algorithms::get_comparator;
-use crate::models::Record;
+use std::cmp::Ordering;
 
-pub fn sort(records: &mut [Record], column_index: usize) -> f64 {
-    let comparator = get_comparator(column_index);
-    let start = std::time::Instant::now();
-    let n = records.len();
+/// Sorts any slice in place with `compare`, untimed. Stable.
+pub fn sort_by<T, F>(slice: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    bubble_sort(slice, &mut compare);
+}
+
+fn bubble_sort<T, F>(slice: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = slice.len();
     for i in 0..n {
         for j in 0..n - i - 1 {
-            if comparator(&records[j], &records[j + 1]) == std::cmp::Ordering::Greater {
-                records.swap(j, j + 1);
+            if compare(&slice[j], &slice[j + 1]) == Ordering::Greater {
+                slice.swap(j, j + 1);
             }
         }
     }
-    start.elapsed().as_secs_f64() * 1000.0
 }
 
 // ----------  TESTS  -------------------------------------------------
 #[cfg(test)]
 mod tests {
-    use super::*; // brings `sort` and `Record` into scope
+    use crate::algorithms::Algorithm;
 
     #[test]
     fn sorts_by_given_column() {
@@ -33,9 +40,9 @@ mod tests {
         ];
 
         // Sort by the third column (the age field, index = 2)
-        // The signature is `fn sort(data: &mut [Record], column_index: usize)`
+        // The signature is `fn Algorithm::Bubble.sort(data: &mut [Record], column_index: usize)`
         // Each element of `data` is a `Vec<String>`, i.e., a `Record`.
-        sort(&mut data, 2);
+        Algorithm::Bubble.sort(&mut data, 2);
 
         // Verify that the first record now holds the smallest age value (22)
         // `data[0].get(2).unwrap()` returns `&String`; comparing it to a `&str`
@@ -43,11 +50,26 @@ mod tests {
         assert_eq!(data[0].get(2).unwrap(), "22");
     }
 
+    #[test]
+    fn sort_indices_matches_sort() {
+        let mut data = vec![
+            vec!["John".into(), "Doe".into(), "25".into()],
+            vec!["Alice".into(), "Smith".into(), "22".into()],
+            vec!["Bob".into(), "Brown".into(), "23".into()],
+        ];
+        let mut expected = data.clone();
+
+        Algorithm::Bubble.sort(&mut expected, 2);
+        Algorithm::Bubble.sort_indices(&mut data, 2);
+
+        assert_eq!(data, expected);
+    }
+
     #[test]
     fn does_nothing_on_empty_slice() {
         // An empty slice should not panic and should return quickly
         let mut empty: Vec<Vec<String>> = Vec::new();
-        let duration = sort(&mut empty, 0);
+        let duration = Algorithm::Bubble.sort(&mut empty, 0);
         // The function should finish quickly; we just assert that it returns a finite f64.
         assert!((0.0..=1.0).contains(&duration));
     }
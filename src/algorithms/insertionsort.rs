@@ -1,28 +1,34 @@
 // Copyright (c) 2026 Neil Pandya
 
-use crate::algorithms::get_comparator;
-use crate::models::Record;
+use std::cmp::Ordering;
 
-pub fn sort(records: &mut [Record], column_index: usize) -> f64 {
-    let comparator = get_comparator(column_index);
-    let start = std::time::Instant::now();
-    let n = records.len();
+/// Sorts any slice in place with `compare`, untimed. Stable.
+pub fn sort_by<T, F>(slice: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    insertion_sort(slice, &mut compare);
+}
+
+fn insertion_sort<T, F>(slice: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = slice.len();
 
     for i in 1..n {
         let mut j = i;
-        while j > 0 && comparator(&records[j - 1], &records[j]) == std::cmp::Ordering::Greater {
-            records.swap(j - 1, j);
+        while j > 0 && compare(&slice[j - 1], &slice[j]) == Ordering::Greater {
+            slice.swap(j - 1, j);
             j -= 1;
         }
     }
-
-    start.elapsed().as_secs_f64() * 1000.0
 }
 
 // ----------  TESTS  -------------------------------------------------
 #[cfg(test)]
 mod tests {
-    use super::*; // brings `sort` and `Record` into scope
+    use crate::algorithms::Algorithm;
 
     #[test]
     fn sorts_small_vec_correctly() {
@@ -36,7 +42,7 @@ mod tests {
         ];
 
         // Sort by the second column (index = 1) – the numeric value.
-        sort(&mut data, 1);
+        Algorithm::Insertion.sort(&mut data, 1);
 
         // After sorting, the keys must be in ascending order.
         // `data.iter().map(|r| r.get(1))` yields `Option<&String>`.
@@ -46,11 +52,26 @@ mod tests {
         assert_eq!(sorted_keys, vec!["1", "2", "3"]); // ascending order
     }
 
+    #[test]
+    fn sort_indices_matches_sort() {
+        let mut data = vec![
+            vec!["c".into(), "1".into()],
+            vec!["a".into(), "3".into()],
+            vec!["b".into(), "2".into()],
+        ];
+        let mut expected = data.clone();
+
+        Algorithm::Insertion.sort(&mut expected, 1);
+        Algorithm::Insertion.sort_indices(&mut data, 1);
+
+        assert_eq!(data, expected);
+    }
+
     #[test]
     fn leaves_empty_slice_unchanged() {
         // An empty slice should not panic and should return a finite duration.
         let mut empty: Vec<Vec<String>> = Vec::new();
-        let duration = sort(&mut empty, 0);
+        let duration = Algorithm::Insertion.sort(&mut empty, 0);
         assert!((0.0..=1.0).contains(&duration));
     }
 }
@@ -1,19 +1,21 @@
 // Copyright (c) 2026 Neil Pandya
 
-use crate::algorithms::get_comparator;
-use crate::models::Record;
 use std::cmp::Ordering;
 
-pub fn sort(records: &mut [Record], column_index: usize) -> f64 {
-    let comparator = get_comparator(column_index);
-    let start = std::time::Instant::now();
-    merge_sort(records, &comparator);
-    start.elapsed().as_secs_f64() * 1000.0
+/// Sorts any slice with `compare`, untimed. Stable; each merge clones into a
+/// scratch buffer, so unlike the other algorithms this needs `T: Clone`.
+pub fn sort_by<T, F>(slice: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    merge_sort(slice, &mut compare);
 }
 
-fn merge_sort<F>(slice: &mut [Record], compare: &F)
+fn merge_sort<T, F>(slice: &mut [T], compare: &mut F)
 where
-    F: Fn(&Record, &Record) -> Ordering,
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
 {
     let mid = slice.len() / 2;
     if mid == 0 {
@@ -52,7 +54,7 @@ where
 // ----------  TESTS  -------------------------------------------------
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::algorithms::Algorithm;
 
     #[test]
     fn sorts_records_correctly() {
@@ -63,17 +65,34 @@ mod tests {
         ];
 
         // Sort by the second column (index 1)
-        sort(&mut data, 1);
+        Algorithm::Merge.sort(&mut data, 1);
 
         let result: Vec<&String> = data.iter().filter_map(|r| r.get(1)).collect();
 
         assert_eq!(result, vec!["1", "2", "3"]);
     }
 
+    #[test]
+    fn sort_indices_matches_sort() {
+        let mut data = vec![
+            vec!["Banana".into(), "3".into()],
+            vec!["Apple".into(), "1".into()],
+            vec!["Cherry".into(), "2".into()],
+            vec!["Apricot".into(), "1".into()],
+        ];
+        let mut expected = data.clone();
+
+        Algorithm::Merge.sort(&mut expected, 1);
+        Algorithm::Merge.sort_indices(&mut data, 1);
+
+        // Both paths are stable, so ties keep their original order.
+        assert_eq!(data, expected);
+    }
+
     #[test]
     fn handles_single_element() {
         let mut data = vec![vec!["Single".into(), "0".into()]];
-        sort(&mut data, 0);
+        Algorithm::Merge.sort(&mut data, 0);
         assert_eq!(data.len(), 1);
         assert_eq!(data[0][0], "Single");
     }
@@ -81,7 +100,7 @@ mod tests {
     #[test]
     fn handles_empty_slice() {
         let mut data: Vec<Vec<String>> = vec![];
-        sort(&mut data, 0);
+        Algorithm::Merge.sort(&mut data, 0);
         assert!(data.is_empty());
     }
 }
@@ -8,26 +8,245 @@ pub mod mergesort;
 pub mod quicksort;
 pub mod standardsort;
 
+use crate::arena::RecordArena;
+use crate::memory::AllocStats;
 use crate::models::Record;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
+/// A sorting algorithm. Each variant's module implements only the generic,
+/// untimed `sort_by`; the timed entry points below are built on top of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Std,
+    Merge,
+    Quick,
+    Bubble,
+    Insertion,
+}
+
+/// O(n log n) algorithms, benchmarked on any row count.
+pub const FAST_ALGORITHMS: [Algorithm; 3] = [Algorithm::Std, Algorithm::Merge, Algorithm::Quick];
+
+/// O(n²) algorithms, skipped on large inputs.
+pub const SLOW_ALGORITHMS: [Algorithm; 2] = [Algorithm::Bubble, Algorithm::Insertion];
+
+impl Algorithm {
+    /// As shown in results, e.g. `Quick Sort`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Std => "Std Sort",
+            Algorithm::Merge => "Merge Sort",
+            Algorithm::Quick => "Quick Sort",
+            Algorithm::Bubble => "Bubble Sort",
+            Algorithm::Insertion => "Insertion Sort",
+        }
+    }
+
+    /// Sorts any slice with this algorithm, untimed. `T: Clone` is only needed
+    /// by merge sort, but applies to all so they are interchangeable.
+    pub fn sort_by<T, F>(&self, slice: &mut [T], compare: F)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        match self {
+            Algorithm::Std => standardsort::sort_by(slice, compare),
+            Algorithm::Merge => mergesort::sort_by(slice, compare),
+            Algorithm::Quick => quicksort::sort_by(slice, compare),
+            Algorithm::Bubble => bubblesort::sort_by(slice, compare),
+            Algorithm::Insertion => insertionsort::sort_by(slice, compare),
+        }
+    }
+
+    /// Sorts `records` by the given column and returns the elapsed time in
+    /// milliseconds.
+    pub fn sort(&self, records: &mut [Record], column_index: usize) -> f64 {
+        self.sort_with(records, get_comparator(column_index))
+    }
+
+    /// `sort_by`, timed: returns the elapsed time in milliseconds. On rows, the
+    /// comparator can be any, e.g. one over several sort keys.
+    pub fn sort_with<T, F>(&self, slice: &mut [T], compare: F) -> f64
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let start = std::time::Instant::now();
+        self.sort_by(slice, compare);
+        start.elapsed().as_secs_f64() * 1000.0
+    }
+
+    /// Index-sort variant: sorts `u32` row indices instead of moving whole
+    /// rows, then applies the permutation once at the end.
+    pub fn sort_indices(&self, records: &mut [Record], column_index: usize) -> f64 {
+        let start = std::time::Instant::now();
+        let mut indices = row_indices(records);
+        self.sort_by(&mut indices, get_index_comparator(records, column_index));
+        apply_permutation(records, &indices);
+        start.elapsed().as_secs_f64() * 1000.0
+    }
+
+    /// Arena-storage variant: sorts row indices against the packed buffer,
+    /// then rebuilds the arena in sorted order.
+    pub fn sort_arena(&self, arena: &mut RecordArena, column_index: usize) -> f64 {
+        let start = std::time::Instant::now();
+        let mut indices = arena_row_indices(arena);
+        self.sort_by(&mut indices, get_arena_comparator(arena, column_index));
+        arena.permute(&indices);
+        start.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// Every algorithm, fast ones first.
+pub fn all_algorithms() -> impl Iterator<Item = Algorithm> {
+    FAST_ALGORITHMS.into_iter().chain(SLOW_ALGORITHMS)
+}
+
+/// Compares two cell values, numerically if both parse as floats.
+pub fn compare_values(val_a: &str, val_b: &str) -> Ordering {
+    if let (Ok(num_a), Ok(num_b)) = (val_a.parse::<f64>(), val_b.parse::<f64>()) {
+        num_a.partial_cmp(&num_b).unwrap_or(Ordering::Equal)
+    } else {
+        val_a.cmp(val_b)
+    }
+}
+
 /// Returns a comparator for the given column index.
 /// Attempts to compare numerically if both values are valid floats.
 pub fn get_comparator(column_index: usize) -> impl Fn(&Record, &Record) -> Ordering {
     move |a, b| {
         let val_a = a.get(column_index).map(|s| s.as_str()).unwrap_or("");
         let val_b = b.get(column_index).map(|s| s.as_str()).unwrap_or("");
+        compare_values(val_a, val_b)
+    }
+}
 
-        if let (Ok(num_a), Ok(num_b)) = (val_a.parse::<f64>(), val_b.parse::<f64>()) {
-            num_a.partial_cmp(&num_b).unwrap_or(Ordering::Equal)
-        } else {
-            val_a.cmp(val_b)
-        }
+/// Returns a comparator over row indices into `records`, ordering them the
+/// same way `get_comparator` orders the rows themselves.
+pub fn get_index_comparator(
+    records: &[Record],
+    column_index: usize,
+) -> impl Fn(&u32, &u32) -> Ordering + '_ {
+    let compare = get_comparator(column_index);
+    move |&a, &b| compare(&records[a as usize], &records[b as usize])
+}
+
+/// Returns a comparator over row indices into an arena, with the same
+/// ordering as `get_comparator`.
+pub fn get_arena_comparator(
+    arena: &RecordArena,
+    column_index: usize,
+) -> impl Fn(&u32, &u32) -> Ordering + '_ {
+    move |&a, &b| {
+        let val_a = arena.cell(a as usize, column_index).unwrap_or("");
+        let val_b = arena.cell(b as usize, column_index).unwrap_or("");
+        compare_values(val_a, val_b)
+    }
+}
+
+/// Builds the identity permutation `0..records.len()` used by the index-sort mode.
+pub fn row_indices(records: &[Record]) -> Vec<u32> {
+    identity_permutation(records.len())
+}
+
+/// Builds the identity permutation over an arena's rows.
+pub fn arena_row_indices(arena: &RecordArena) -> Vec<u32> {
+    identity_permutation(arena.len())
+}
+
+fn identity_permutation(len: usize) -> Vec<u32> {
+    let len = u32::try_from(len).expect("index sort supports at most u32::MAX rows");
+    (0..len).collect()
+}
+
+/// Reorders `records` so that position `i` holds the row previously at `indices[i]`.
+/// Rows are moved, never cloned.
+pub fn apply_permutation(records: &mut [Record], indices: &[u32]) {
+    let mut taken: Vec<Record> = records.iter_mut().map(std::mem::take).collect();
+    for (slot, &index) in records.iter_mut().zip(indices) {
+        *slot = std::mem::take(&mut taken[index as usize]);
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchResult {
     pub name: String,
+    /// The median of `samples_ms` when several trials ran.
     pub duration_ms: f64,
+    /// Heap activity during the run, when allocation tracking was enabled.
+    pub alloc_stats: Option<AllocStats>,
+    /// Every trial's time, when more than one trial ran.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples_ms: Vec<f64>,
+    /// A placeholder for an algorithm that did not run, or a note saying why;
+    /// it has no time, so reports and comparisons leave it out.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+}
+
+impl BenchResult {
+    /// A `skipped` placeholder or note named `name`.
+    pub fn skipped(name: String) -> Self {
+        BenchResult {
+            name,
+            duration_ms: 0.0,
+            alloc_stats: None,
+            samples_ms: Vec::new(),
+            skipped: true,
+        }
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_algorithm_sorts_an_arena_by_column() {
+        for algorithm in all_algorithms() {
+            let mut arena = RecordArena::new();
+            arena.push_row(["Delta", "40"]);
+            arena.push_row(["Alpha", "10"]);
+            arena.push_row(["Charlie", "30"]);
+            arena.push_row(["Bravo", "20"]);
+
+            algorithm.sort_arena(&mut arena, 1);
+
+            let names: Vec<&str> = (0..arena.len()).filter_map(|r| arena.cell(r, 0)).collect();
+            assert_eq!(
+                names,
+                vec!["Alpha", "Bravo", "Charlie", "Delta"],
+                "{}",
+                algorithm.name()
+            );
+        }
+    }
+
+    #[test]
+    fn every_sort_by_orders_any_element_type() {
+        for algorithm in all_algorithms() {
+            let mut ints = vec![3, -1, 2, 0];
+            algorithm.sort_by(&mut ints, |a, b| b.cmp(a));
+            assert_eq!(ints, vec![3, 2, 0, -1], "{}", algorithm.name());
+
+            let mut floats = vec![2.5, -0.5, 10.0, 1.0];
+            algorithm.sort_by(&mut floats, f64::total_cmp);
+            assert_eq!(floats, vec![-0.5, 1.0, 2.5, 10.0], "{}", algorithm.name());
+
+            // Quick sort is the only unstable one.
+            if algorithm != Algorithm::Quick {
+                let mut words = vec!["pear", "fig", "apple", "kiwi"];
+                algorithm.sort_by(&mut words, |a, b| a.len().cmp(&b.len()));
+                // Equal lengths keep their input order.
+                assert_eq!(
+                    words,
+                    vec!["fig", "pear", "kiwi", "apple"],
+                    "{}",
+                    algorithm.name()
+                );
+            }
+        }
+    }
 }
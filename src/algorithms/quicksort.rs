@@ -1,19 +1,19 @@
 // Copyright 2026 Neil Pandya
 
-use crate::algorithms::get_comparator;
-use crate::models::Record;
 use std::cmp::Ordering;
 
-pub fn sort(records: &mut [Record], column_index: usize) -> f64 {
-    let comparator = get_comparator(column_index);
-    let start = std::time::Instant::now();
-    quick_sort(records, &comparator);
-    start.elapsed().as_secs_f64() * 1000.0
+/// Sorts any slice in place with `compare`, untimed: Lomuto partitioning
+/// around the middle element. Not stable.
+pub fn sort_by<T, F>(slice: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quick_sort(slice, &mut compare);
 }
 
-fn quick_sort<F>(slice: &mut [Record], compare: &F)
+fn quick_sort<T, F>(slice: &mut [T], compare: &mut F)
 where
-    F: Fn(&Record, &Record) -> Ordering,
+    F: FnMut(&T, &T) -> Ordering,
 {
     if slice.len() <= 1 {
         return;
@@ -23,9 +23,9 @@ where
     quick_sort(&mut slice[pivot_index + 1..], compare);
 }
 
-fn partition<F>(slice: &mut [Record], compare: &F) -> usize
+fn partition<T, F>(slice: &mut [T], compare: &mut F) -> usize
 where
-    F: Fn(&Record, &Record) -> Ordering,
+    F: FnMut(&T, &T) -> Ordering,
 {
     let pivot_index = slice.len() / 2;
     slice.swap(pivot_index, slice.len() - 1);
@@ -43,7 +43,7 @@ where
 // ----------  TESTS  -------------------------------------------------
 #[cfg(test)]
 mod tests {
-    use super::*; // brings `sort`, `Record`, and any helper functions into scope
+    use crate::algorithms::Algorithm;
 
     #[test]
     fn sorts_records_by_numeric_column() {
@@ -56,7 +56,7 @@ mod tests {
         ];
 
         // Sort by the second column (index 1), which contains numeric strings
-        sort(&mut data, 1);
+        Algorithm::Quick.sort(&mut data, 1);
 
         // Collect the sorted keys (second column values)
         let sorted_values: Vec<&String> = data.iter().filter_map(|r| r.get(1)).collect();
@@ -65,11 +65,26 @@ mod tests {
         assert_eq!(sorted_values, vec!["10", "20", "30", "40"]);
     }
 
+    #[test]
+    fn sort_indices_orders_rows_by_column() {
+        let mut data = vec![
+            vec!["Delta".into(), "40".into()],
+            vec!["Alpha".into(), "10".into()],
+            vec!["Charlie".into(), "30".into()],
+            vec!["Beta".into(), "20".into()],
+        ];
+
+        Algorithm::Quick.sort_indices(&mut data, 1);
+
+        let names: Vec<&String> = data.iter().filter_map(|r| r.first()).collect();
+        assert_eq!(names, vec!["Alpha", "Beta", "Charlie", "Delta"]);
+    }
+
     #[test]
     fn handles_single_record() {
         let mut data = vec![vec!["Only".into(), "999".into()]];
 
-        sort(&mut data, 0);
+        Algorithm::Quick.sort(&mut data, 0);
 
         assert_eq!(data[0][0], "Only");
         assert_eq!(data[0][1], "999");
@@ -78,7 +93,7 @@ mod tests {
     #[test]
     fn leaves_empty_slice_unchanged() {
         let mut empty: Vec<Vec<String>> = Vec::new();
-        let duration = sort(&mut empty, 0);
+        let duration = Algorithm::Quick.sort(&mut empty, 0);
         assert!((0.0..=1.0).contains(&duration));
     }
 }
@@ -1,19 +1,19 @@
 // Copyright (c) 2026 Neil Pandya
 
-use crate::algorithms::get_comparator;
-use crate::models::Record;
-
-pub fn sort(records: &mut [Record], column_index: usize) -> f64 {
-    let comparator = get_comparator(column_index);
-    let start = std::time::Instant::now();
-    records.sort_by(comparator);
-    start.elapsed().as_secs_f64() * 1000.0
+use std::cmp::Ordering;
+
+/// The standard library's stable sort on any slice, untimed.
+pub fn sort_by<T, F>(slice: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    slice.sort_by(compare);
 }
 
 // ----------  TESTS  -------------------------------------------------
 #[cfg(test)]
 mod tests {
-    use super::*; // brings `sort`, `Record`, and any helper functions into scope
+    use crate::algorithms::Algorithm;
 
     #[test]
     fn sorts_records_by_string_column() {
@@ -25,10 +25,10 @@ mod tests {
         ];
 
         // Sort by the first column (index 0), which contains string keys
-        sort(&mut data, 0);
+        Algorithm::Std.sort(&mut data, 0);
 
         // Collect the sorted keys (first column values)
-        let sorted_keys: Vec<&String> = data.iter().filter_map(|r| r.get(0)).collect();
+        let sorted_keys: Vec<&String> = data.iter().filter_map(|r| r.first()).collect();
 
         // Assert that the keys are in lexicographic order
         assert_eq!(sorted_keys, vec!["apple", "banana", "zebra"]);
@@ -44,7 +44,7 @@ mod tests {
         ];
 
         // Sort by the second column (index 1), which contains numeric strings
-        sort(&mut data, 1);
+        Algorithm::Std.sort(&mut data, 1);
 
         let sorted_values: Vec<&String> = data.iter().filter_map(|r| r.get(1)).collect();
 
@@ -52,11 +52,26 @@ mod tests {
         assert_eq!(sorted_values, vec!["3", "20", "100"]);
     }
 
+    #[test]
+    fn sort_indices_matches_sort() {
+        let mut data = vec![
+            vec!["ItemA".into(), "100".into()],
+            vec!["ItemB".into(), "20".into()],
+            vec!["ItemC".into(), "3".into()],
+        ];
+        let mut expected = data.clone();
+
+        Algorithm::Std.sort(&mut expected, 1);
+        Algorithm::Std.sort_indices(&mut data, 1);
+
+        assert_eq!(data, expected);
+    }
+
     #[test]
     fn handles_single_record() {
         let mut data = vec![vec!["Only".into(), "999".into()]];
 
-        sort(&mut data, 0);
+        Algorithm::Std.sort(&mut data, 0);
 
         assert_eq!(data[0][0], "Only");
         assert_eq!(data[0][1], "999");
@@ -65,7 +80,7 @@ mod tests {
     #[test]
     fn leaves_empty_slice_unchanged() {
         let mut empty: Vec<Vec<String>> = Vec::new();
-        let duration = sort(&mut empty, 0);
+        let duration = Algorithm::Std.sort(&mut empty, 0);
         assert!((0.0..=1.0).contains(&duration));
     }
 }
@@ -0,0 +1,171 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Arena-backed record storage: every cell lives in one contiguous `String`
+// buffer, addressed by per-cell end offsets and per-row start offsets.
+
+use crate::models::Record;
+use std::mem::{size_of, size_of_val};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordArena {
+    /// All cell contents, concatenated without separators.
+    buffer: String,
+    /// Byte offset in `buffer` where each cell ends.
+    cell_ends: Vec<usize>,
+    /// Index into `cell_ends` of each row's first cell, plus a trailing sentinel.
+    row_starts: Vec<usize>,
+}
+
+impl RecordArena {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            cell_ends: Vec::new(),
+            row_starts: vec![0],
+        }
+    }
+
+    /// Packs already-loaded rows, for inputs without a dedicated arena loader.
+    pub fn from_records(records: &[Record]) -> Self {
+        let mut arena = Self::new();
+        for record in records {
+            arena.push_row(record.iter().map(|s| s.as_str()));
+        }
+        arena.shrink_to_fit();
+        arena
+    }
+
+    pub fn push_row<'a>(&mut self, cells: impl IntoIterator<Item = &'a str>) {
+        for cell in cells {
+            self.buffer.push_str(cell);
+            self.cell_ends.push(self.buffer.len());
+        }
+        self.row_starts.push(self.cell_ends.len());
+    }
+
+    pub fn len(&self) -> usize {
+        self.row_starts.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the given cell, or `None` if the row is shorter than `column_index`.
+    pub fn cell(&self, row: usize, column_index: usize) -> Option<&str> {
+        let first = self.row_starts[row];
+        let cell = first + column_index;
+        if cell >= self.row_starts[row + 1] {
+            return None;
+        }
+        let start = if cell == 0 {
+            0
+        } else {
+            self.cell_ends[cell - 1]
+        };
+        Some(&self.buffer[start..self.cell_ends[cell]])
+    }
+
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &str> {
+        let width = self.row_starts[row + 1] - self.row_starts[row];
+        (0..width).filter_map(move |column| self.cell(row, column))
+    }
+
+    /// Rebuilds the arena so that row `i` holds the row previously at `indices[i]`.
+    pub fn permute(&mut self, indices: &[u32]) {
+        let mut sorted = Self {
+            buffer: String::with_capacity(self.buffer.len()),
+            cell_ends: Vec::with_capacity(self.cell_ends.len()),
+            row_starts: Vec::with_capacity(self.row_starts.len()),
+        };
+        sorted.row_starts.push(0);
+        for &index in indices {
+            sorted.push_row(self.row(index as usize));
+        }
+        *self = sorted;
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.buffer.shrink_to_fit();
+        self.cell_ends.shrink_to_fit();
+        self.row_starts.shrink_to_fit();
+    }
+
+    /// Heap bytes reserved by the arena's three buffers.
+    pub fn heap_size(&self) -> usize {
+        self.buffer.capacity()
+            + self.cell_ends.capacity() * size_of::<usize>()
+            + self.row_starts.capacity() * size_of::<usize>()
+    }
+}
+
+impl Default for RecordArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Heap bytes reserved by `Vec<Vec<String>>` storage, for comparison with
+/// `RecordArena::heap_size`.
+pub fn records_heap_size(records: &[Record]) -> usize {
+    size_of_val(records)
+        + records
+            .iter()
+            .map(|r| {
+                r.capacity() * size_of::<String>() + r.iter().map(String::capacity).sum::<usize>()
+            })
+            .sum::<usize>()
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_records(arena: &RecordArena) -> Vec<Record> {
+        (0..arena.len())
+            .map(|row| arena.row(row).map(|s| s.to_string()).collect())
+            .collect()
+    }
+
+    fn sample() -> Vec<Record> {
+        vec![
+            vec!["Alice".into(), "30".into()],
+            vec!["".into(), "25".into()],
+            vec!["Carol".into()], // ragged row
+        ]
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let arena = RecordArena::from_records(&sample());
+
+        assert_eq!(arena.len(), 3);
+        assert_eq!(arena.cell(0, 0), Some("Alice"));
+        assert_eq!(arena.cell(1, 0), Some(""));
+        assert_eq!(arena.cell(2, 1), None);
+        assert_eq!(to_records(&arena), sample());
+    }
+
+    #[test]
+    fn permute_reorders_rows() {
+        let mut arena = RecordArena::from_records(&sample());
+
+        arena.permute(&[2, 0, 1]);
+
+        let rows = to_records(&arena);
+        assert_eq!(rows[0], vec!["Carol"]);
+        assert_eq!(rows[1], vec!["Alice", "30"]);
+        assert_eq!(rows[2], vec!["", "25"]);
+    }
+
+    #[test]
+    fn arena_is_smaller_than_nested_vectors() {
+        let records: Vec<Record> = (0..100)
+            .map(|i| vec![format!("name{}", i), i.to_string()])
+            .collect();
+        let arena = RecordArena::from_records(&records);
+
+        assert!(arena.heap_size() < records_heap_size(&records));
+    }
+}
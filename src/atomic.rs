@@ -0,0 +1,200 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Crash-safe file replacement: write to a temp file beside the destination,
+// check it, then rename it into place so readers never see a partial file.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `path` with `.bak` appended, e.g. `data.csv.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Replaces `path` with `bytes` atomically; see `write_atomic_with`.
+pub fn write_atomic(
+    path: &Path,
+    bytes: &[u8],
+    backup: bool,
+    verify: impl FnOnce(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    write_atomic_with(path, backup, |out| out.write_all(bytes), verify)
+}
+
+/// Replaces `path` atomically with whatever `write` streams into a buffered
+/// temp file beside it. The temp file is synced, then `verify` (e.g. a row
+/// count of the parsed-back file) must accept it before the rename. With
+/// `backup`, an existing file is kept as `backup_path(path)`. On any failure
+/// before the rename the destination and any previous backup are left untouched.
+/// If only the backup cannot be moved into place afterwards, the new file still
+/// counts as written: the staged copy is removed and a warning printed instead.
+pub fn write_atomic_with<E: From<io::Error>>(
+    path: &Path,
+    backup: bool,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), E>,
+    verify: impl FnOnce(&Path) -> io::Result<()>,
+) -> Result<(), E> {
+    let temp = temp_path(path);
+    let mut pending_backup = None;
+    let result = write_and_verify(&temp, path, write, verify).and_then(|()| {
+        if backup && path.exists() {
+            pending_backup = Some(stage_backup(path)?);
+        }
+        Ok(fs::rename(&temp, path)?)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        if let Some(staged) = &pending_backup {
+            let _ = fs::remove_file(staged);
+        }
+        return result;
+    }
+    if let Some(staged) = pending_backup
+        && let Err(e) = fs::rename(&staged, backup_path(path))
+    {
+        let _ = fs::remove_file(&staged);
+        eprintln!(
+            "warning: {} was saved, but its backup could not be kept: {}",
+            path.display(),
+            e
+        );
+    }
+    sync_parent(path);
+    Ok(())
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(format!(".{}.{}.tmp", std::process::id(), nanos));
+    path.with_file_name(name)
+}
+
+fn write_and_verify<E: From<io::Error>>(
+    temp: &Path,
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), E>,
+    verify: impl FnOnce(&Path) -> io::Result<()>,
+) -> Result<(), E> {
+    let mut out = BufWriter::new(File::create_new(temp)?);
+    write(&mut out)?;
+    let file = out.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+    drop(file);
+    // Keep the permissions of the file being replaced.
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(temp, metadata.permissions())?;
+    }
+
+    Ok(verify(temp)?)
+}
+
+/// Hard-links the current file to a temp name beside its backup, copying where
+/// links are unsupported, so the destination never disappears. The caller
+/// renames it over the old backup only once the new file is in place.
+fn stage_backup(path: &Path) -> io::Result<PathBuf> {
+    let staged = temp_path(&backup_path(path));
+    match fs::hard_link(path, &staged).or_else(|_| fs::copy(path, &staged).map(drop)) {
+        Ok(()) => Ok(staged),
+        Err(e) => {
+            let _ = fs::remove_file(&staged);
+            Err(e)
+        }
+    }
+}
+
+/// Makes the rename itself durable where the platform allows it.
+fn sync_parent(path: &Path) {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty())
+        && let Ok(dir) = File::open(parent)
+    {
+        let _ = dir.sync_all();
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn replaces_the_file_and_keeps_a_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, b"new", true, |_| Ok(())).unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert_eq!(fs::read(backup_path(&path)).unwrap(), b"old");
+        assert_eq!(entries(dir.path()), vec!["data.csv", "data.csv.bak"]);
+    }
+
+    #[test]
+    fn failed_verification_leaves_the_destination_untouched() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, "old").unwrap();
+
+        let result = write_atomic(&path, b"new", true, |written| {
+            assert_eq!(fs::read(written).unwrap(), b"new");
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "row count mismatch",
+            ))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+        assert_eq!(entries(dir.path()), vec!["data.csv"]);
+    }
+
+    #[test]
+    fn a_failed_backup_keeps_the_previous_one() {
+        let dir = TempDir::new().unwrap();
+        // A directory can be neither linked nor copied as a backup.
+        let path = dir.path().join("data.csv");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("inside"), "x").unwrap();
+        fs::write(backup_path(&path), "older").unwrap();
+
+        assert!(write_atomic(&path, b"new", true, |_| Ok(())).is_err());
+
+        assert_eq!(fs::read(backup_path(&path)).unwrap(), b"older");
+        assert_eq!(entries(dir.path()), vec!["data.csv", "data.csv.bak"]);
+    }
+
+    #[test]
+    fn a_backup_that_cannot_be_renamed_still_saves_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, "old").unwrap();
+        // A non-empty directory cannot be replaced by the staged backup.
+        fs::create_dir(backup_path(&path)).unwrap();
+        fs::write(backup_path(&path).join("inside"), "x").unwrap();
+
+        write_atomic(&path, b"new", true, |_| Ok(())).unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert!(backup_path(&path).join("inside").exists());
+        assert_eq!(entries(dir.path()), vec!["data.csv", "data.csv.bak"]);
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Benchmark harness shared by the GUI and the CLI.
+
+use crate::algorithms::{self, Algorithm, BenchResult};
+use crate::arena::RecordArena;
+use crate::memory;
+use crate::models::Record;
+use std::cmp::Ordering;
+
+pub const BUBBLE_SORT_ROW_LIMIT: usize = 1000; // Bubble/insertion sort gets slow beyond this
+
+/// Unoptimised builds make the hand-written sorts look many times slower than
+/// they are, so their timings are flagged everywhere they are shown.
+pub const DEBUG_BUILD: bool = cfg!(debug_assertions);
+
+pub const DEBUG_BUILD_WARNING: &str = "This is a debug build: timings are many times slower \
+     than a release build and not comparable with release results. \
+     Rebuild with `cargo build --release`.";
+
+/// Which extra variants to run alongside the default move-the-rows sort.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BenchOptions<'a> {
+    /// Also benchmark each algorithm sorting row indices instead of whole rows.
+    pub compare_index_sort: bool,
+    /// Also benchmark each algorithm on this arena copy of the data.
+    pub arena: Option<&'a RecordArena>,
+    /// Count heap allocations during each run (see `memory::measure_allocations`).
+    pub track_allocations: bool,
+    /// Times to run every algorithm; 0 and 1 both mean a single run.
+    pub trials: usize,
+}
+
+/// Runs one timed sort, optionally under allocation tracking.
+pub fn measure(name: String, track_allocations: bool, run: impl FnOnce() -> f64) -> BenchResult {
+    let (duration_ms, alloc_stats) = if track_allocations {
+        let (duration_ms, stats) = memory::measure_allocations(run);
+        (duration_ms, Some(stats))
+    } else {
+        (run(), None)
+    };
+    BenchResult {
+        name,
+        duration_ms,
+        alloc_stats,
+        samples_ms: Vec::new(),
+        skipped: false,
+    }
+}
+
+/// Runs every applicable algorithm on its own copy of `records`, `options.trials`
+/// times. With several trials each result reports the median time, keeps every
+/// trial's time in `samples_ms`, and the allocation stats of the first trial.
+pub fn run_benchmarks(
+    records: &[Record],
+    column_index: usize,
+    options: BenchOptions,
+) -> Vec<BenchResult> {
+    repeat_trials(options.trials, || run_once(records, column_index, options))
+}
+
+/// Benchmarks every applicable algorithm's generic `sort_by` on its own copy of
+/// `values` (integers, floats, strings, structs, ...), with the trials,
+/// allocation tracking and row limit of `run_benchmarks`. The index-sort and
+/// arena variants only apply to CSV rows and are ignored here.
+pub fn run_value_benchmarks<T, F>(
+    values: &[T],
+    compare: F,
+    options: BenchOptions,
+) -> Vec<BenchResult>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering + Clone,
+{
+    repeat_trials(options.trials, || {
+        let mut results = Vec::new();
+        for algorithm in applicable_algorithms(values.len(), &mut results) {
+            let mut data = values.to_vec();
+            let compare = compare.clone();
+            results.push(measure(
+                algorithm.name().to_string(),
+                options.track_allocations,
+                || algorithm.sort_with(&mut data, compare),
+            ));
+        }
+        results
+    })
+}
+
+/// Runs `trial` `trials` times, merging the results as `run_benchmarks` describes.
+fn repeat_trials(trials: usize, mut trial: impl FnMut() -> Vec<BenchResult>) -> Vec<BenchResult> {
+    let mut results = trial();
+    if trials <= 1 {
+        return results;
+    }
+
+    for result in &mut results {
+        result.samples_ms.push(result.duration_ms);
+    }
+    for _ in 1..trials {
+        // Every trial yields the same algorithms in the same order.
+        for (result, trial) in results.iter_mut().zip(trial()) {
+            result.samples_ms.push(trial.duration_ms);
+        }
+    }
+    for result in &mut results {
+        result.duration_ms = median(&result.samples_ms);
+    }
+    results
+}
+
+pub fn median(samples: &[f64]) -> f64 {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => sorted[n / 2],
+        n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+    }
+}
+
+fn run_once(records: &[Record], column_index: usize, options: BenchOptions) -> Vec<BenchResult> {
+    let mut results = Vec::new();
+
+    let track = options.track_allocations;
+    for algorithm in applicable_algorithms(records.len(), &mut results) {
+        let mut data = records.to_vec();
+        results.push(measure(algorithm.name().to_string(), track, || {
+            algorithm.sort(&mut data, column_index)
+        }));
+
+        if options.compare_index_sort {
+            let mut data = records.to_vec();
+            results.push(measure(
+                format!("{} (index)", algorithm.name()),
+                track,
+                || algorithm.sort_indices(&mut data, column_index),
+            ));
+        }
+
+        if let Some(arena) = options.arena {
+            let mut data = arena.clone();
+            results.push(measure(
+                format!("{} (arena)", algorithm.name()),
+                track,
+                || algorithm.sort_arena(&mut data, column_index),
+            ));
+        }
+    }
+
+    results
+}
+
+/// The algorithms to run on `row_count` rows. Quadratic ones are skipped on
+/// large inputs, with placeholder results explaining why pushed to `results`.
+fn applicable_algorithms(row_count: usize, results: &mut Vec<BenchResult>) -> Vec<Algorithm> {
+    let skip_slow = row_count > BUBBLE_SORT_ROW_LIMIT;
+
+    let mut algorithms: Vec<Algorithm> = algorithms::FAST_ALGORITHMS.to_vec();
+
+    if !skip_slow {
+        algorithms.extend_from_slice(&algorithms::SLOW_ALGORITHMS);
+    } else {
+        // Add a note explaining why some algorithms were skipped
+        for name in ["Bubble Sort", "Insertion Sort", "(skipped: >1000 rows)"] {
+            results.push(BenchResult::skipped(name.to_string()));
+        }
+    }
+
+    algorithms
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(rows: usize) -> Vec<Record> {
+        (0..rows)
+            .rev()
+            .map(|i| vec![format!("row{}", i), i.to_string()])
+            .collect()
+    }
+
+    #[test]
+    fn runs_every_algorithm_on_small_inputs() {
+        let results = run_benchmarks(&sample(10), 1, BenchOptions::default());
+
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Std Sort",
+                "Merge Sort",
+                "Quick Sort",
+                "Bubble Sort",
+                "Insertion Sort"
+            ]
+        );
+    }
+
+    #[test]
+    fn adds_variants_when_requested() {
+        let records = sample(10);
+        let arena = RecordArena::default();
+        let options = BenchOptions {
+            compare_index_sort: true,
+            arena: Some(&arena),
+            ..Default::default()
+        };
+
+        let results = run_benchmarks(&records, 1, options);
+
+        assert_eq!(results.len(), 15);
+        assert_eq!(results[1].name, "Std Sort (index)");
+        assert_eq!(results[2].name, "Std Sort (arena)");
+    }
+
+    #[test]
+    fn tracks_allocations_when_requested() {
+        let options = BenchOptions {
+            track_allocations: true,
+            ..Default::default()
+        };
+
+        let results = run_benchmarks(&sample(10), 1, options);
+
+        let merge = results.iter().find(|r| r.name == "Merge Sort").unwrap();
+        let quick = results.iter().find(|r| r.name == "Quick Sort").unwrap();
+        // Merge sort clones rows into a scratch buffer; quick sort works in place.
+        assert!(merge.alloc_stats.unwrap().allocations > 0);
+        assert_eq!(quick.alloc_stats.unwrap().allocations, 0);
+    }
+
+    #[test]
+    fn repeats_trials_and_reports_the_median() {
+        let options = BenchOptions {
+            trials: 3,
+            ..Default::default()
+        };
+
+        let results = run_benchmarks(&sample(10), 1, options);
+
+        for result in &results {
+            assert_eq!(result.samples_ms.len(), 3);
+            assert_eq!(result.duration_ms, median(&result.samples_ms));
+        }
+        assert_eq!(median(&[3.0, 1.0, 2.0, 10.0]), 2.5);
+    }
+
+    #[test]
+    fn benchmarks_plain_values_with_every_algorithm() {
+        #[derive(Clone)]
+        struct Point {
+            x: f64,
+        }
+        let options = BenchOptions {
+            trials: 2,
+            track_allocations: true,
+            ..Default::default()
+        };
+
+        let ints: Vec<i64> = (0..50).rev().collect();
+        let results = run_value_benchmarks(&ints, i64::cmp, options);
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|r| r.samples_ms.len() == 2));
+        let quick = results.iter().find(|r| r.name == "Quick Sort").unwrap();
+        assert_eq!(quick.alloc_stats.unwrap().allocations, 0);
+
+        let points: Vec<Point> = (0..=BUBBLE_SORT_ROW_LIMIT)
+            .map(|i| Point { x: i as f64 / 3.0 })
+            .collect();
+        let results = run_value_benchmarks(
+            &points,
+            |a: &Point, b: &Point| b.x.total_cmp(&a.x),
+            BenchOptions::default(),
+        );
+        assert!(results.iter().any(|r| r.name.starts_with("(skipped")));
+    }
+
+    #[test]
+    fn skips_quadratic_algorithms_on_large_inputs() {
+        let results = run_benchmarks(
+            &sample(BUBBLE_SORT_ROW_LIMIT + 1),
+            1,
+            BenchOptions::default(),
+        );
+
+        assert!(results.iter().any(|r| r.name.starts_with("(skipped")));
+        assert_eq!(results.len(), 6);
+        assert_eq!(results.iter().filter(|r| r.skipped).count(), 3);
+    }
+}
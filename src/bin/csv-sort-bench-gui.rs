@@ -0,0 +1,20 @@
+// Copyright (c) 2026 Neil Pandya
+
+// The egui front-end, built with the `gui` feature.
+
+use csv_sort_bench::{gui, memory};
+use std::process::ExitCode;
+
+// Counts nothing unless a benchmark opts in via `memory::measure_allocations`.
+#[global_allocator]
+static GLOBAL: memory::CountingAllocator = memory::CountingAllocator;
+
+fn main() -> ExitCode {
+    match gui::run_app() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to start GUI: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
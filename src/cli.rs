@@ -0,0 +1,765 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Headless front-end: runs the same benchmarks as the GUI and prints the
+// results and the pipeline breakdown to stdout.
+
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use csv_sort_bench::algorithms::{self, Algorithm};
+use csv_sort_bench::arena::RecordArena;
+use csv_sort_bench::bench::{self, BenchOptions};
+use csv_sort_bench::compression::Compression;
+use csv_sort_bench::dialect::{
+    ExportOptions, LineTerminator, LoadOptions, Quoting, RaggedRows, parse_dialect_char,
+};
+use csv_sort_bench::encoding;
+use csv_sort_bench::history::History;
+use csv_sort_bench::io;
+use csv_sort_bench::memory;
+use csv_sort_bench::models::CsvError;
+use csv_sort_bench::pipeline::{self, PipelineTimings};
+use csv_sort_bench::readers::{FixedWidthReader, InputFormat, NdjsonReader, ParquetReader};
+use csv_sort_bench::regression::{self, RegressionConfig, Status, Tolerance};
+use csv_sort_bench::report::{BenchParams, BenchReport, DatasetInfo, Environment, ReportFormat};
+use csv_sort_bench::sort_keys::{self, KeySpec};
+use csv_sort_bench::suite::Suite;
+use csv_sort_bench::writers::OutputFormat;
+use encoding_rs::Encoding;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "Benchmark sorting algorithms on a CSV file",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// CSV file to benchmark
+    #[arg(required = true)]
+    pub input: Option<PathBuf>,
+
+    /// Input format: csv, ndjson, parquet or fixed-width (default: from the
+    /// input's extension, e.g. `.jsonl` or `.parquet`)
+    #[arg(long, value_parser = parse_format)]
+    pub format: Option<InputFormat>,
+
+    /// Column spec for fixed-width input, e.g. `id:4,name:12,age:3`
+    /// (implies `--format fixed-width`; add `--headers` to skip a title line)
+    #[arg(long)]
+    pub fixed_width: Option<String>,
+
+    /// Column to sort by, as a header name or a zero-based index
+    #[arg(short, long, default_value = "0")]
+    pub column: String,
+
+    /// Write the sorted data to this path
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Also benchmark each algorithm sorting row indices instead of whole rows
+    #[arg(long)]
+    pub index_sort: bool,
+
+    /// Also benchmark each algorithm on arena-backed storage
+    #[arg(long)]
+    pub arena: bool,
+
+    /// Count heap allocations and peak live bytes during each algorithm's run
+    #[arg(long)]
+    pub track_allocations: bool,
+
+    /// Save the benchmark results, with the dataset and parameters, to this path
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Report format: csv, json or markdown (default: from the report's
+    /// extension, e.g. `.json` or `.md`)
+    #[arg(long, value_parser = parse_report_format)]
+    pub report_format: Option<ReportFormat>,
+
+    /// Record this run in the benchmark history
+    #[arg(long)]
+    pub history: bool,
+
+    /// Run each algorithm this many times and report the median
+    #[arg(long, default_value = "1")]
+    pub trials: usize,
+
+    /// JSON report of an earlier run (from `--report x.json`) to check this
+    /// run against; exits non-zero if any algorithm regressed
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Slowdown in milliseconds tolerated before a baseline regression
+    #[arg(long, default_value = "0")]
+    pub max_slowdown_ms: f64,
+
+    /// Slowdown in percent tolerated before a baseline regression
+    #[arg(long, default_value = "10")]
+    pub max_slowdown_pct: f64,
+
+    /// Per-algorithm tolerance such as `Bubble Sort=25%` or `Std Sort=2ms`;
+    /// may be repeated
+    #[arg(long, value_parser = parse_tolerance)]
+    pub tolerance: Vec<(String, String)>,
+
+    /// Only count a slowdown as a regression if a Mann-Whitney U test over the
+    /// trials is significant at this level, e.g. 0.05 (needs `--trials` >= 2)
+    #[arg(long)]
+    pub significance: Option<f64>,
+
+    #[command(flatten)]
+    pub load: LoadArgs,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run every benchmark described by a TOML suite file
+    Suite(SuiteArgs),
+    /// Sort a CSV file (or stdin) by one or more columns and write it to stdout
+    Sort(SortArgs),
+}
+
+#[derive(Debug, ClapArgs)]
+pub struct SortArgs {
+    /// CSV file to sort; stdin when omitted or `-`
+    pub input: Option<PathBuf>,
+
+    /// Sort key as COLUMN[:num|:text][:asc|:desc], by header name or
+    /// zero-based index; repeat for tie-breakers (default: the first column)
+    #[arg(short, long = "key")]
+    pub keys: Vec<String>,
+
+    /// Algorithm: std, merge, quick, bubble or insertion (std and merge are stable)
+    #[arg(short, long, value_parser = parse_algorithm, default_value = "std")]
+    pub algorithm: Algorithm,
+
+    /// Write to this path instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub load: LoadArgs,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+}
+
+#[derive(Debug, ClapArgs)]
+pub struct SuiteArgs {
+    /// Suite file
+    pub file: PathBuf,
+
+    /// Save the combined results to this path
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Report format: csv, json or markdown (default: from the report's
+    /// extension, e.g. `.json` or `.md`)
+    #[arg(long, value_parser = parse_report_format)]
+    pub report_format: Option<ReportFormat>,
+}
+
+/// Overrides for the sniffed dialect; anything left unset keeps the sniffed value.
+#[derive(Debug, Default, ClapArgs)]
+pub struct LoadArgs {
+    /// Field delimiter (a single character, or `\t` for tab)
+    #[arg(long, value_parser = parse_char)]
+    pub delimiter: Option<u8>,
+
+    /// Quote character
+    #[arg(long, value_parser = parse_char)]
+    pub quote: Option<u8>,
+
+    /// Escape character inside quoted fields (default: doubled quotes)
+    #[arg(long, value_parser = parse_char)]
+    pub escape: Option<u8>,
+
+    /// Text encoding, by WHATWG label (e.g. utf-8, windows-1252, utf-16le)
+    #[arg(long, value_parser = parse_encoding_label)]
+    pub encoding: Option<&'static Encoding>,
+
+    /// Skip lines starting with this character
+    #[arg(long, value_parser = parse_char)]
+    pub comment: Option<u8>,
+
+    /// Treat the first row as a header row
+    #[arg(long, conflicts_with = "no_headers")]
+    pub headers: bool,
+
+    /// Treat the first row as data and name columns column_1..column_n
+    #[arg(long)]
+    pub no_headers: bool,
+
+    /// Strip leading and trailing whitespace from every field
+    #[arg(long)]
+    pub trim: bool,
+
+    /// What to do with rows whose field count differs from the header's:
+    /// error, keep, pad (pad or truncate) or skip (default: error)
+    #[arg(long, value_parser = parse_ragged_rows)]
+    pub ragged_rows: Option<RaggedRows>,
+}
+
+impl LoadArgs {
+    /// Sniffs `path` and applies the overrides on top.
+    pub fn resolve(&self, path: &Path) -> LoadOptions {
+        self.apply(LoadOptions::from(io::detect_dialect(path)))
+    }
+
+    /// Applies the overrides on top of already sniffed options.
+    pub fn apply(&self, mut options: LoadOptions) -> LoadOptions {
+        let dialect = &mut options.dialect;
+        dialect.delimiter = self.delimiter.unwrap_or(dialect.delimiter);
+        dialect.quote = self.quote.unwrap_or(dialect.quote);
+        dialect.escape = self.escape.or(dialect.escape);
+        dialect.encoding = self.encoding.unwrap_or(dialect.encoding);
+        if self.headers || self.no_headers {
+            dialect.has_headers = self.headers;
+        }
+        options.comment = self.comment;
+        options.trim = self.trim;
+        options.ragged_rows = self.ragged_rows.unwrap_or_default();
+        options
+    }
+
+    /// Rejects the CSV-only flags for other input formats, which would
+    /// otherwise ignore them. Fixed-width input still takes `--headers`, to
+    /// skip a title line.
+    pub fn check_format(&self, format: InputFormat) -> Result<(), String> {
+        if format == InputFormat::Csv {
+            return Ok(());
+        }
+        let given = [
+            ("--delimiter", self.delimiter.is_some()),
+            ("--quote", self.quote.is_some()),
+            ("--escape", self.escape.is_some()),
+            ("--encoding", self.encoding.is_some()),
+            ("--comment", self.comment.is_some()),
+            (
+                "--headers",
+                self.headers && format != InputFormat::FixedWidth,
+            ),
+            ("--no-headers", self.no_headers),
+            ("--trim", self.trim),
+            ("--ragged-rows", self.ragged_rows.is_some()),
+        ];
+        match given.into_iter().find(|&(_, given)| given) {
+            Some((flag, _)) => Err(format!(
+                "{} only applies to CSV input, not {}",
+                flag,
+                format.name()
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Overrides for the exported dialect, which otherwise mirrors the input's.
+#[derive(Debug, Default, ClapArgs)]
+pub struct ExportArgs {
+    /// Output format: csv, json, ndjson, markdown, html or sqlite (default:
+    /// from the output's extension, e.g. `.json` or `.sqlite`)
+    #[arg(long, value_parser = parse_output_format)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Output field delimiter
+    #[arg(long, value_parser = parse_char)]
+    pub output_delimiter: Option<u8>,
+
+    /// Which output fields to quote: necessary, always or non-numeric
+    #[arg(long, value_parser = parse_quoting)]
+    pub output_quoting: Option<Quoting>,
+
+    /// Output line terminator: lf or crlf
+    #[arg(long, value_parser = parse_terminator)]
+    pub output_terminator: Option<LineTerminator>,
+
+    /// Start the output with a byte-order mark
+    #[arg(long, conflicts_with = "no_output_bom")]
+    pub output_bom: bool,
+
+    /// Never write a byte-order mark, even if the input had one
+    #[arg(long)]
+    pub no_output_bom: bool,
+
+    /// Write the output in the input's encoding instead of UTF-8
+    #[arg(long)]
+    pub keep_encoding: bool,
+
+    /// Compress the output: none, gzip, zstd or bzip2 (default: from the
+    /// output's extension, e.g. `.csv.gz`)
+    #[arg(long, value_parser = parse_compression)]
+    pub compression: Option<Compression>,
+
+    /// Keep an existing output file as `<output>.bak`
+    #[arg(long)]
+    pub backup: bool,
+}
+
+impl ExportArgs {
+    /// Mirrors the input's dialect and applies the overrides on top.
+    pub fn resolve(&self, load_options: &LoadOptions, output: Option<&Path>) -> ExportOptions {
+        let mut options = ExportOptions::from(load_options);
+        if self.keep_encoding {
+            options = options.keep_encoding(&load_options.dialect);
+        }
+        options.delimiter = self.output_delimiter.unwrap_or(options.delimiter);
+        options.quoting = self.output_quoting.unwrap_or(options.quoting);
+        options.terminator = self.output_terminator.unwrap_or(options.terminator);
+        if self.output_bom || self.no_output_bom {
+            options.bom = self.output_bom;
+        }
+        options.compression = self
+            .compression
+            .or(output.map(Compression::from_path))
+            .unwrap_or_default();
+        options.backup = self.backup;
+        options
+    }
+
+    /// The requested output format, else the one `output`'s extension implies.
+    pub fn format(&self, output: Option<&Path>) -> OutputFormat {
+        self.output_format
+            .or(output.map(OutputFormat::from_path))
+            .unwrap_or_default()
+    }
+}
+
+fn parse_char(text: &str) -> Result<u8, String> {
+    parse_dialect_char(text)
+        .ok_or_else(|| format!("expected a single ASCII character, got {:?}", text))
+}
+
+fn parse_encoding_label(text: &str) -> Result<&'static Encoding, String> {
+    encoding::parse_encoding(text).ok_or_else(|| format!("unknown encoding: {}", text))
+}
+
+/// Matches `text` against the command-line names of `choices`.
+fn parse_choice<T: Copy>(
+    text: &str,
+    choices: &[T],
+    name: fn(&T) -> &'static str,
+) -> Result<T, String> {
+    choices
+        .iter()
+        .find(|choice| name(choice) == text)
+        .copied()
+        .ok_or_else(|| {
+            let names: Vec<&str> = choices.iter().map(name).collect();
+            format!("expected one of {}", names.join(", "))
+        })
+}
+
+/// Matches an algorithm by its name without " Sort", case-insensitively.
+fn parse_algorithm(text: &str) -> Result<Algorithm, String> {
+    let short_name = |algorithm: &Algorithm| {
+        algorithm
+            .name()
+            .trim_end_matches(" Sort")
+            .to_ascii_lowercase()
+    };
+    algorithms::all_algorithms()
+        .find(|algorithm| short_name(algorithm) == text.to_ascii_lowercase())
+        .ok_or_else(|| {
+            let names: Vec<String> = algorithms::all_algorithms()
+                .map(|a| short_name(&a))
+                .collect();
+            format!("expected one of {}", names.join(", "))
+        })
+}
+
+fn parse_format(text: &str) -> Result<InputFormat, String> {
+    parse_choice(text, &InputFormat::ALL, InputFormat::name)
+}
+
+fn parse_output_format(text: &str) -> Result<OutputFormat, String> {
+    parse_choice(text, &OutputFormat::ALL, OutputFormat::name)
+}
+
+fn parse_report_format(text: &str) -> Result<ReportFormat, String> {
+    parse_choice(text, &ReportFormat::ALL, ReportFormat::name)
+}
+
+/// Splits `NAME=LIMIT`; the limit is checked by `Tolerance::with_limit`.
+fn parse_tolerance(text: &str) -> Result<(String, String), String> {
+    let (name, limit) = text
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected NAME=LIMIT, got {:?}", text))?;
+    Tolerance::default().with_limit(limit)?;
+    Ok((name.trim().to_string(), limit.to_string()))
+}
+
+fn parse_compression(text: &str) -> Result<Compression, String> {
+    parse_choice(text, &Compression::ALL, Compression::name)
+}
+
+fn parse_ragged_rows(text: &str) -> Result<RaggedRows, String> {
+    parse_choice(text, &RaggedRows::ALL, RaggedRows::name)
+}
+
+fn parse_quoting(text: &str) -> Result<Quoting, String> {
+    parse_choice(text, &Quoting::ALL, Quoting::name)
+}
+
+fn parse_terminator(text: &str) -> Result<LineTerminator, String> {
+    parse_choice(text, &LineTerminator::ALL, LineTerminator::name)
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    // The sort filter reports no timings, so it needs no debug-build warning.
+    if let Some(Command::Sort(sort_args)) = &args.command {
+        return run_sort(sort_args);
+    }
+    if bench::DEBUG_BUILD {
+        eprintln!("warning: {}", bench::DEBUG_BUILD_WARNING);
+    }
+    if let Some(Command::Suite(suite_args)) = &args.command {
+        return run_suite(suite_args);
+    }
+    let input = args
+        .input
+        .as_ref()
+        .expect("clap requires an input without a subcommand");
+    // Read the baseline first so a bad path fails before the benchmarks run.
+    let baseline = args
+        .baseline
+        .as_deref()
+        .map(BenchReport::load)
+        .transpose()?;
+    let regression_config = args.regression_config(baseline.as_ref())?;
+    let environment = Environment::capture();
+    let format = match (args.format, &args.fixed_width) {
+        (Some(format), _) => format,
+        (None, Some(_)) => InputFormat::FixedWidth,
+        (None, None) => InputFormat::from_path(input),
+    };
+    if args.fixed_width.is_some() && format != InputFormat::FixedWidth {
+        return Err(format!("--fixed-width does not apply to {} input", format.name()).into());
+    }
+    args.load.check_format(format)?;
+    // Only CSV has a dialect to sniff; the other formats export with the defaults.
+    let load_options = match format {
+        InputFormat::Csv => args.load.resolve(input),
+        _ => LoadOptions::default(),
+    };
+    let fixed_width;
+    let reader: &dyn io::TableReader = match format {
+        InputFormat::Csv => &load_options,
+        InputFormat::Ndjson => &NdjsonReader,
+        InputFormat::Parquet => &ParquetReader,
+        InputFormat::FixedWidth => {
+            let spec = args
+                .fixed_width
+                .as_deref()
+                .ok_or("--format fixed-width needs a --fixed-width column spec")?;
+            fixed_width = FixedWidthReader::from_spec(spec, args.load.headers)?;
+            &fixed_width
+        }
+    };
+    let io::Loaded {
+        headers,
+        rows: mut records,
+        issues,
+        mut timings,
+    } = io::load_table_timed(input, reader)?;
+    let column_index = io::resolve_column(&headers, &args.column)
+        .ok_or_else(|| format!("unknown column: {}", args.column))?;
+
+    println!(
+        "Loaded {} rows x {} columns from {}",
+        records.len(),
+        headers.len(),
+        input.display()
+    );
+    match format {
+        InputFormat::Csv => println!("Dialect: {}", load_options.dialect),
+        format => println!("Format: {}", format.name()),
+    }
+    if !issues.is_empty() {
+        eprintln!("{} ragged or unreadable row(s):", issues.len());
+        for issue in &issues {
+            eprintln!("  {}", issue);
+        }
+    }
+    println!("Sorting by column \"{}\"", headers[column_index]);
+
+    let arena = match (args.arena, format) {
+        (false, _) => None,
+        (true, InputFormat::Csv) => Some(io::load_csv_arena(input, &load_options)?.rows),
+        // The arena loaders only speak CSV; pack the rows already loaded instead.
+        (true, _) => Some(RecordArena::from_records(&records)),
+    };
+    let options = BenchOptions {
+        compare_index_sort: args.index_sort,
+        arena: arena.as_ref(),
+        track_allocations: args.track_allocations,
+        trials: args.trials,
+    };
+    let results = bench::run_benchmarks(&records, column_index, options);
+
+    println!();
+    if args.track_allocations {
+        println!(
+            "{:<28} {:>12} {:>14} {:>12} {:>14}",
+            "Algorithm", "Time (ms)", "Allocated", "Allocs", "Peak live"
+        );
+    } else {
+        println!("{:<28} {:>12}", "Algorithm", "Time (ms)");
+    }
+    for result in &results {
+        if result.skipped {
+            println!("{:<28} {:>12}", result.name, "-");
+            continue;
+        }
+        match result.alloc_stats {
+            Some(stats) => println!(
+                "{:<28} {:>12.3} {:>14} {:>12} {:>14}",
+                result.name,
+                result.duration_ms,
+                memory::format_bytes(stats.bytes_allocated),
+                stats.allocations,
+                memory::format_bytes(stats.peak_live_bytes)
+            ),
+            None => println!("{:<28} {:>12.3}", result.name, result.duration_ms),
+        }
+    }
+    if bench::DEBUG_BUILD {
+        println!("(debug build: these timings are not representative)");
+    }
+
+    pipeline::sort_with_keys(&mut records, column_index, &mut timings);
+    let export_options = args.export.resolve(&load_options, args.output.as_deref());
+    let writer = args
+        .export
+        .format(args.output.as_deref())
+        .writer(&export_options);
+    match &args.output {
+        Some(path) => io::save_table(
+            path,
+            writer.as_ref(),
+            &headers,
+            &records,
+            export_options.backup,
+            &mut timings,
+        )?,
+        // No destination: still measure serialization, but skip the write.
+        None => io::serialize_table_timed(writer.as_ref(), &headers, &records, &mut timings)?,
+    }
+
+    println!();
+    println!("Pipeline (ms)");
+    for (stage, ms) in timings.stages() {
+        println!("  {:<16} {:>10.3}", stage, ms);
+    }
+    println!("  {:<16} {:>10.3}", "total", timings.total_ms());
+
+    println!();
+    println!("Environment");
+    for (label, value) in environment.fields() {
+        println!("  {:<16} {}", label, value);
+    }
+
+    let report = BenchReport {
+        dataset: DatasetInfo {
+            path: input.display().to_string(),
+            format: format.name().to_string(),
+            rows: records.len(),
+            columns: headers.len(),
+        },
+        column: headers[column_index].clone(),
+        params: BenchParams::from(&options),
+        results,
+        pipeline: timings,
+        environment,
+    };
+    if let Some(path) = &args.report {
+        let report_format = args
+            .report_format
+            .unwrap_or_else(|| ReportFormat::from_path(path));
+        report.save(path, report_format)?;
+        println!();
+        println!("Report written to {}", path.display());
+    }
+    let checks = baseline
+        .as_ref()
+        .map(|baseline| regression::check(baseline, &report, &regression_config))
+        .transpose()?;
+    if args.history {
+        let path = History::default_path().ok_or(
+            "could not record the run: this platform has no data directory for the history",
+        )?;
+        let mut history = History::open(&path)?;
+        let entry = history.record(report)?;
+        println!("Recorded as run #{} in {}", entry.id, path.display());
+    }
+
+    if let (Some(checks), Some(path)) = (checks, &args.baseline) {
+        println!();
+        println!("Baseline {}", path.display());
+        print!("{}", regression::format_checks(&checks));
+        let regressed = checks
+            .iter()
+            .filter(|check| check.status == Status::Regressed)
+            .count();
+        if regressed > 0 {
+            return Err(
+                format!("{} algorithm(s) regressed against the baseline", regressed).into(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+impl Args {
+    /// Also rejects `--significance` when either run would have too few
+    /// samples for the test, rather than quietly skipping it.
+    fn regression_config(
+        &self,
+        baseline: Option<&BenchReport>,
+    ) -> Result<RegressionConfig, String> {
+        let default = Tolerance {
+            abs_ms: self.max_slowdown_ms,
+            rel_pct: self.max_slowdown_pct,
+        };
+        let per_algorithm = self
+            .tolerance
+            .iter()
+            .map(|(name, limit)| Ok((name.clone(), default.with_limit(limit)?)))
+            .collect::<Result<_, String>>()?;
+        if let Some(alpha) = self.significance
+            && !(alpha > 0.0 && alpha < 1.0)
+        {
+            return Err(format!(
+                "--significance must be between 0 and 1, got {}",
+                alpha
+            ));
+        }
+        if self.significance.is_some() {
+            if self.trials < 2 {
+                return Err("--significance needs --trials 2 or more".to_string());
+            }
+            if let Some(baseline) = baseline
+                && baseline
+                    .results
+                    .iter()
+                    .any(|r| !r.skipped && r.samples_ms.len() < 2)
+            {
+                return Err(
+                    "--significance needs a baseline recorded with --trials 2 or more".to_string(),
+                );
+            }
+        }
+        Ok(RegressionConfig {
+            default,
+            per_algorithm,
+            significance: self.significance,
+        })
+    }
+}
+
+fn run_sort(args: &SortArgs) -> Result<(), Box<dyn Error>> {
+    let path = args.input.as_deref().filter(|path| *path != Path::new("-"));
+    let input = match path {
+        Some(path) => io::open_input(path)?,
+        None => io::open_reader(std::io::stdin().lock())?,
+    };
+    let (dialect, input) = io::sniff_input(input)?;
+    let load_options = args.load.apply(LoadOptions::from(dialect));
+    let mut issues = Vec::new();
+    let (headers, mut records) =
+        io::parse_csv(input, &load_options, &mut issues).map_err(|e| match path {
+            Some(path) => e.with_path(path),
+            None => e,
+        })?;
+    for issue in &issues {
+        eprintln!("warning: {}", issue);
+    }
+    // Empty input has no columns to sort by; pass it through unchanged.
+    if headers.is_empty() {
+        if let Some(output) = &args.output {
+            std::fs::write(output, b"").map_err(|e| CsvError::from(e).with_path(output))?;
+        }
+        return Ok(());
+    }
+
+    let specs = match args.keys.as_slice() {
+        [] => vec![KeySpec::parse("0")?],
+        keys => keys
+            .iter()
+            .map(|key| KeySpec::parse(key))
+            .collect::<Result<_, _>>()?,
+    };
+    let keys = specs
+        .iter()
+        .map(|spec| spec.resolve(&headers, &records))
+        .collect::<Result<Vec<_>, _>>()?;
+    args.algorithm
+        .sort_with(&mut records, sort_keys::compare_rows(&keys));
+
+    let export_options = args.export.resolve(&load_options, args.output.as_deref());
+    let writer = args
+        .export
+        .format(args.output.as_deref())
+        .writer(&export_options);
+    match &args.output {
+        Some(output) => io::save_table(
+            output,
+            writer.as_ref(),
+            &headers,
+            &records,
+            export_options.backup,
+            &mut PipelineTimings::default(),
+        )?,
+        None => match io::write_table_to(
+            std::io::stdout().lock(),
+            writer.as_ref(),
+            &headers,
+            &records,
+        ) {
+            // The reader went away (e.g. `| head`), as any filter allows.
+            Err(CsvError::Io { source, .. }) if source.kind() == std::io::ErrorKind::BrokenPipe => {
+            }
+            result => result?,
+        },
+    }
+    Ok(())
+}
+
+fn run_suite(args: &SuiteArgs) -> Result<(), Box<dyn Error>> {
+    let suite = Suite::load(&args.file)?;
+    let report = suite.run(&mut |group| eprintln!("Running {}", group))?;
+
+    println!(
+        "{:<20} {:<14} {:<16} {:<28} {:>12}",
+        "Dataset", "Distribution", "Column", "Algorithm", "Time (ms)"
+    );
+    for (run, result) in report.rows() {
+        println!(
+            "{:<20} {:<14} {:<16} {:<28} {:>12.3}",
+            run.dataset,
+            run.distribution.name(),
+            run.report.column,
+            result.name,
+            result.duration_ms
+        );
+    }
+
+    if let Some(path) = &args.report {
+        let report_format = args
+            .report_format
+            .unwrap_or_else(|| ReportFormat::from_path(path));
+        report.save(path, report_format)?;
+        println!();
+        println!("Report written to {}", path.display());
+    }
+    Ok(())
+}
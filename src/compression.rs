@@ -0,0 +1,268 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Transparent gzip/zstd/bzip2 support: compressed inputs are recognised by
+// magic bytes or extension and decompressed on load; exports can be compressed.
+
+#[cfg(not(all(feature = "zstd", feature = "bzip2")))]
+use crate::models::unsupported;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Extensions that name uncompressed text, whatever the first bytes look like.
+const PLAIN_TEXT_EXTENSIONS: [&str; 2] = ["csv", "tsv"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Compression {
+    pub const ALL: [Compression; 4] = [
+        Compression::None,
+        Compression::Gzip,
+        Compression::Zstd,
+        Compression::Bzip2,
+    ];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+        }
+    }
+
+    /// File extension without the dot, e.g. `gz` for `data.csv.gz`.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+            Compression::Bzip2 => Some("bz2"),
+        }
+    }
+
+    /// Guesses from the extension alone; used for output paths.
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        Self::ALL
+            .into_iter()
+            .find(|c| {
+                c.extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Recognises the format by its magic bytes. bzip2's `BZh` must be followed
+    /// by its block size digit, so text like `BZh,count` is not taken for it.
+    pub fn from_magic(header: &[u8]) -> Self {
+        if header.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else if let [b'B', b'Z', b'h', b'1'..=b'9', ..] = header {
+            Compression::Bzip2
+        } else {
+            Compression::None
+        }
+    }
+
+    /// A `.csv` or `.tsv` extension always means uncompressed text, since a
+    /// header like `BZh,count` would pass for bzip2. Otherwise magic bytes win,
+    /// and the extension is only trusted when the header is too short to tell.
+    pub fn detect(path: &Path, header: &[u8]) -> Self {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if PLAIN_TEXT_EXTENSIONS
+            .iter()
+            .any(|plain| plain.eq_ignore_ascii_case(extension))
+        {
+            return Compression::None;
+        }
+        match Self::from_magic(header) {
+            Compression::None if header.len() < 4 => Self::from_path(path),
+            compression => compression,
+        }
+    }
+
+    /// Whether this build can read and write the format; zstd and bzip2 are
+    /// behind Cargo features of the same name.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Compression::None | Compression::Gzip => true,
+            Compression::Zstd => cfg!(feature = "zstd"),
+            Compression::Bzip2 => cfg!(feature = "bzip2"),
+        }
+    }
+
+    /// Wraps `reader` in the matching streaming decompressor.
+    pub fn decompress_reader<'a, R: Read + 'a>(&self, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Compression::None => Box::new(reader),
+            Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => return Err(unsupported("zstd", "zstd")),
+            #[cfg(not(feature = "bzip2"))]
+            Compression::Bzip2 => return Err(unsupported("bzip2", "bzip2")),
+        })
+    }
+
+    pub fn decompress(&self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(bytes.len() * 4);
+        self.decompress_reader(bytes)?.read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    /// Compresses `bytes` at the format's default level.
+    pub fn compress(&self, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        if *self == Compression::None {
+            return Ok(bytes);
+        }
+        let mut encoder = self.compress_writer(Vec::new())?;
+        encoder.write_all(&bytes)?;
+        encoder.finish()
+    }
+
+    /// Wraps `out` in the matching streaming compressor, at the format's
+    /// default level. `CompressWriter::finish` writes the trailer.
+    pub fn compress_writer<W: Write>(&self, out: W) -> io::Result<CompressWriter<W>> {
+        Ok(match self {
+            Compression::None => CompressWriter::None(out),
+            Compression::Gzip => CompressWriter::Gzip(flate2::write::GzEncoder::new(
+                out,
+                flate2::Compression::default(),
+            )),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => CompressWriter::Zstd(zstd::Encoder::new(out, 0)?),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => CompressWriter::Bzip2(bzip2::write::BzEncoder::new(
+                out,
+                bzip2::Compression::default(),
+            )),
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => return Err(unsupported("zstd", "zstd")),
+            #[cfg(not(feature = "bzip2"))]
+            Compression::Bzip2 => return Err(unsupported("bzip2", "bzip2")),
+        })
+    }
+}
+
+/// A writer that compresses on the fly; see `Compression::compress_writer`.
+pub enum CompressWriter<W: Write> {
+    None(W),
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, W>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<W>),
+}
+
+impl<W: Write> CompressWriter<W> {
+    /// Ends the stream and returns the underlying writer.
+    pub fn finish(self) -> io::Result<W> {
+        match self {
+            CompressWriter::None(out) => Ok(out),
+            CompressWriter::Gzip(encoder) => encoder.finish(),
+            #[cfg(feature = "zstd")]
+            CompressWriter::Zstd(encoder) => encoder.finish(),
+            #[cfg(feature = "bzip2")]
+            CompressWriter::Bzip2(encoder) => encoder.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for CompressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            CompressWriter::None(out) => out.write(buf),
+            CompressWriter::Gzip(encoder) => encoder.write(buf),
+            #[cfg(feature = "zstd")]
+            CompressWriter::Zstd(encoder) => encoder.write(buf),
+            #[cfg(feature = "bzip2")]
+            CompressWriter::Bzip2(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            CompressWriter::None(out) => out.flush(),
+            CompressWriter::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "zstd")]
+            CompressWriter::Zstd(encoder) => encoder.flush(),
+            #[cfg(feature = "bzip2")]
+            CompressWriter::Bzip2(encoder) => encoder.flush(),
+        }
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_format_and_detects_it_by_magic() {
+        let text = b"name,age\nAlice,30\nBob,25\n".to_vec();
+        for compression in Compression::ALL
+            .into_iter()
+            .filter(Compression::is_supported)
+        {
+            let compressed = compression.compress(text.clone()).unwrap();
+            assert_eq!(Compression::from_magic(&compressed), compression);
+            assert_eq!(compression.decompress(&compressed).unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_extension_for_short_headers() {
+        assert_eq!(
+            Compression::from_path(Path::new("a.csv.GZ")),
+            Compression::Gzip
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("a.csv")),
+            Compression::None
+        );
+        assert_eq!(
+            Compression::detect(Path::new("a.csv.zst"), b""),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::detect(Path::new("a.csv.zst"), b"name,age\n"),
+            Compression::None
+        );
+    }
+
+    #[test]
+    fn a_csv_extension_beats_lookalike_magic_bytes() {
+        assert_eq!(
+            Compression::detect(Path::new("counts.CSV"), b"BZh,count\n"),
+            Compression::None
+        );
+        assert_eq!(
+            Compression::detect(Path::new("counts.bz2"), b"BZh9\x31\x41"),
+            Compression::Bzip2
+        );
+    }
+
+    #[test]
+    fn a_header_starting_with_bzh_is_not_bzip2() {
+        assert_eq!(Compression::from_magic(b"BZh,count\n"), Compression::None);
+        assert_eq!(Compression::from_magic(b"BZhx"), Compression::None);
+        assert_eq!(
+            Compression::detect(Path::new("counts"), b"BZh,count\n"),
+            Compression::None
+        );
+        assert_eq!(Compression::from_magic(b"BZh91AY&SY"), Compression::Bzip2);
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Row-level load diagnostics: what a lenient load did to each ragged or
+// unreadable row, so the front-ends can report it instead of aborting.
+
+use crate::dialect::RaggedRows;
+use crate::models::CsvError;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowAction {
+    /// Loaded with its original field count.
+    Kept,
+    /// Missing trailing fields were filled with empty strings.
+    Padded,
+    /// Extra trailing fields were dropped.
+    Truncated,
+    /// Left out of the loaded data.
+    Skipped,
+}
+
+impl fmt::Display for RowAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RowAction::Kept => "kept as-is",
+            RowAction::Padded => "padded",
+            RowAction::Truncated => "truncated",
+            RowAction::Skipped => "skipped",
+        })
+    }
+}
+
+/// One row that did not match the expected shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowIssue {
+    /// 1-based line number where the row starts.
+    pub line: u64,
+    pub reason: String,
+    pub action: RowAction,
+}
+
+impl fmt::Display for RowIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} ({})", self.line, self.reason, self.action)
+    }
+}
+
+/// Applies a `RaggedRows` policy row by row and records what it did.
+pub struct RowChecker<'a> {
+    policy: RaggedRows,
+    expected: usize,
+    issues: &'a mut Vec<RowIssue>,
+}
+
+impl<'a> RowChecker<'a> {
+    pub fn new(policy: RaggedRows, expected: usize, issues: &'a mut Vec<RowIssue>) -> Self {
+        Self {
+            policy,
+            expected,
+            issues,
+        }
+    }
+
+    /// Returns the field count the row should be stored with, or `None` to skip it.
+    pub fn check(&mut self, line: u64, width: usize) -> Option<usize> {
+        if width == self.expected {
+            return Some(width);
+        }
+        let (action, stored) = match self.policy {
+            // A strict reader rejects ragged rows before they get here.
+            RaggedRows::Error | RaggedRows::Keep => (RowAction::Kept, Some(width)),
+            RaggedRows::PadOrTruncate if width < self.expected => {
+                (RowAction::Padded, Some(self.expected))
+            }
+            RaggedRows::PadOrTruncate => (RowAction::Truncated, Some(self.expected)),
+            RaggedRows::Skip => (RowAction::Skipped, None),
+        };
+        self.issues.push(RowIssue {
+            line,
+            reason: format!("{} fields, expected {}", width, self.expected),
+            action,
+        });
+        stored
+    }
+
+    /// Handles a row the reader could not parse: fatal under `RaggedRows::Error`
+    /// (and for I/O failures, which would repeat), otherwise recorded and skipped.
+    pub fn reject(&mut self, error: csv::Error) -> Result<(), CsvError> {
+        self.reject_row(error.into())
+    }
+
+    /// `reject` for problems found after the reader accepted the row.
+    pub fn reject_row(&mut self, error: CsvError) -> Result<(), CsvError> {
+        match error {
+            CsvError::Parse { line, message, .. } if self.policy != RaggedRows::Error => {
+                self.issues.push(RowIssue {
+                    line: line.unwrap_or(0),
+                    reason: message,
+                    action: RowAction::Skipped,
+                });
+                Ok(())
+            }
+            error => Err(error),
+        }
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_short_rows_and_truncates_long_ones() {
+        let mut issues = Vec::new();
+        let mut checker = RowChecker::new(RaggedRows::PadOrTruncate, 3, &mut issues);
+
+        assert_eq!(checker.check(2, 3), Some(3));
+        assert_eq!(checker.check(3, 2), Some(3));
+        assert_eq!(checker.check(4, 5), Some(3));
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].to_string(),
+            "line 3: 2 fields, expected 3 (padded)"
+        );
+        assert_eq!(issues[1].action, RowAction::Truncated);
+    }
+
+    #[test]
+    fn skips_ragged_rows() {
+        let mut issues = Vec::new();
+        let mut checker = RowChecker::new(RaggedRows::Skip, 2, &mut issues);
+
+        assert_eq!(checker.check(5, 1), None);
+        assert_eq!(issues[0].action, RowAction::Skipped);
+    }
+}
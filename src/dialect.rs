@@ -0,0 +1,643 @@
+// Copyright (c) 2026 Neil Pandya
+
+// CSV dialect sniffing: works out the delimiter, quote character, escape style
+// and header presence from a sample of the file instead of its first line.
+
+use crate::compression::Compression;
+use crate::encoding;
+use encoding_rs::{Encoding, UTF_8};
+use std::fmt;
+
+pub const DELIMITER_CANDIDATES: [u8; 4] = [b',', b';', b'\t', b'|'];
+const QUOTE_CANDIDATES: [u8; 2] = [b'"', b'\''];
+
+/// How many bytes of a file the sniffer looks at.
+pub const SNIFF_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// The formatting conventions of one CSV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dialect {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// Escape character inside quoted fields; `None` means quotes are escaped
+    /// by doubling them (`""`).
+    pub escape: Option<u8>,
+    pub has_headers: bool,
+    /// Text encoding of the file; loaders transcode it to UTF-8.
+    pub encoding: &'static Encoding,
+    /// The file starts with a byte-order mark.
+    pub bom: bool,
+    pub terminator: LineTerminator,
+    /// Which fields the file puts in quotes.
+    pub quoting: Quoting,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            has_headers: true,
+            encoding: UTF_8,
+            bom: false,
+            terminator: LineTerminator::default(),
+            quoting: Quoting::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineTerminator {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineTerminator {
+    pub const ALL: [LineTerminator; 2] = [LineTerminator::Lf, LineTerminator::CrLf];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineTerminator::Lf => "lf",
+            LineTerminator::CrLf => "crlf",
+        }
+    }
+
+    fn csv_terminator(&self) -> csv::Terminator {
+        match self {
+            LineTerminator::Lf => csv::Terminator::Any(b'\n'),
+            LineTerminator::CrLf => csv::Terminator::CRLF,
+        }
+    }
+}
+
+/// Which fields get quotes on output; sniffed so exports quote like the source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Quoting {
+    /// Only fields containing the delimiter, a quote or a line break.
+    #[default]
+    Necessary,
+    Always,
+    /// Every field that does not parse as a number.
+    NonNumeric,
+}
+
+impl Quoting {
+    pub const ALL: [Quoting; 3] = [Quoting::Necessary, Quoting::Always, Quoting::NonNumeric];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quoting::Necessary => "necessary",
+            Quoting::Always => "always",
+            Quoting::NonNumeric => "non-numeric",
+        }
+    }
+
+    fn quote_style(&self) -> csv::QuoteStyle {
+        match self {
+            Quoting::Necessary => csv::QuoteStyle::Necessary,
+            Quoting::Always => csv::QuoteStyle::Always,
+            Quoting::NonNumeric => csv::QuoteStyle::NonNumeric,
+        }
+    }
+}
+
+impl Dialect {
+    /// A `csv::ReaderBuilder` configured for this dialect.
+    pub fn reader_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .escape(self.escape)
+            .double_quote(self.escape.is_none());
+        builder
+    }
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let escape = match self.escape {
+            Some(byte) => format!("'{}'", format_dialect_char(byte)),
+            None => "doubled quotes".to_string(),
+        };
+        write!(
+            f,
+            "delimiter '{}', quote '{}', escape {}, quoting {}, {}, {}, {}{}",
+            format_dialect_char(self.delimiter),
+            format_dialect_char(self.quote),
+            escape,
+            self.quoting.name(),
+            self.terminator.name().to_uppercase(),
+            if self.has_headers {
+                "header row"
+            } else {
+                "no header row"
+            },
+            self.encoding.name(),
+            if self.bom { " with BOM" } else { "" }
+        )
+    }
+}
+
+/// Everything that controls how a file is parsed: the (sniffed or overridden)
+/// dialect plus reader settings that cannot be sniffed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    pub dialect: Dialect,
+    /// Lines starting with this byte are skipped.
+    pub comment: Option<u8>,
+    /// Strip leading and trailing whitespace from every field.
+    pub trim: bool,
+    /// What to do with rows whose field count differs from the header's.
+    pub ragged_rows: RaggedRows,
+}
+
+/// Policy for rows whose field count differs from the header's, and (in the
+/// lenient policies) for rows the reader cannot parse at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// Abort the load on the first bad row.
+    #[default]
+    Error,
+    /// Keep ragged rows with their own field count.
+    Keep,
+    /// Pad short rows with empty fields and drop extra fields from long ones.
+    PadOrTruncate,
+    /// Leave ragged and unparsable rows out.
+    Skip,
+}
+
+impl RaggedRows {
+    pub const ALL: [RaggedRows; 4] = [
+        RaggedRows::Error,
+        RaggedRows::Keep,
+        RaggedRows::PadOrTruncate,
+        RaggedRows::Skip,
+    ];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RaggedRows::Error => "error",
+            RaggedRows::Keep => "keep",
+            RaggedRows::PadOrTruncate => "pad",
+            RaggedRows::Skip => "skip",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            RaggedRows::Error => "Fail on the first bad row",
+            RaggedRows::Keep => "Keep ragged rows as-is",
+            RaggedRows::PadOrTruncate => "Pad / truncate to the header width",
+            RaggedRows::Skip => "Skip bad rows",
+        }
+    }
+}
+
+impl From<Dialect> for LoadOptions {
+    fn from(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            ..Self::default()
+        }
+    }
+}
+
+impl LoadOptions {
+    /// A `csv::ReaderBuilder` configured with every option.
+    pub fn reader_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = self.dialect.reader_builder();
+        builder
+            .has_headers(self.dialect.has_headers)
+            .comment(self.comment)
+            .flexible(self.ragged_rows != RaggedRows::Error)
+            .trim(if self.trim {
+                csv::Trim::All
+            } else {
+                csv::Trim::None
+            });
+        builder
+    }
+}
+
+/// How sorted data is written back out. Defaults to the source file's dialect;
+/// front-ends may override any field per export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// `None` escapes quotes by doubling them.
+    pub escape: Option<u8>,
+    pub quoting: Quoting,
+    pub terminator: LineTerminator,
+    /// Write the header row; off for files that were loaded without one, so
+    /// generated `column_N` names don't leak into the output.
+    pub write_headers: bool,
+    /// Output encoding; UTF-8 unless the user asks to keep the source encoding.
+    pub encoding: &'static Encoding,
+    /// Start the output with a byte-order mark (UTF-8 and UTF-16 only).
+    pub bom: bool,
+    /// Compress the encoded output; front-ends pick it from the output extension.
+    pub compression: Compression,
+    /// Keep an existing destination file as `<name>.bak` when replacing it.
+    pub backup: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        let dialect = Dialect::default();
+        Self {
+            delimiter: dialect.delimiter,
+            quote: dialect.quote,
+            escape: dialect.escape,
+            quoting: dialect.quoting,
+            terminator: dialect.terminator,
+            write_headers: true,
+            encoding: UTF_8,
+            bom: false,
+            compression: Compression::None,
+            backup: false,
+        }
+    }
+}
+
+impl From<&LoadOptions> for ExportOptions {
+    /// Mirrors the input file's dialect, but not its encoding (see `keep_encoding`).
+    fn from(options: &LoadOptions) -> Self {
+        let dialect = &options.dialect;
+        Self {
+            delimiter: dialect.delimiter,
+            quote: dialect.quote,
+            escape: dialect.escape,
+            quoting: dialect.quoting,
+            terminator: dialect.terminator,
+            write_headers: dialect.has_headers,
+            bom: dialect.bom,
+            ..Self::default()
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Writes the output in the source file's encoding instead of UTF-8.
+    pub fn keep_encoding(mut self, dialect: &Dialect) -> Self {
+        self.encoding = dialect.encoding;
+        self
+    }
+
+    /// A `csv::ReaderBuilder` that reads the output back, header row included.
+    pub fn reader_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .escape(self.escape)
+            .double_quote(self.escape.is_none())
+            .has_headers(false)
+            .flexible(true);
+        builder
+    }
+
+    /// A `csv::WriterBuilder` configured with every option.
+    pub fn writer_builder(&self) -> csv::WriterBuilder {
+        let mut builder = csv::WriterBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .quote_style(self.quoting.quote_style())
+            .terminator(self.terminator.csv_terminator())
+            .double_quote(self.escape.is_none())
+            .escape(self.escape.unwrap_or(b'\\'));
+        builder
+    }
+}
+
+/// Parses a dialect character as typed by a user: a single ASCII character,
+/// or `\t` / `tab` for a tab.
+pub fn parse_dialect_char(text: &str) -> Option<u8> {
+    match text {
+        "\\t" | "tab" => Some(b'\t'),
+        _ if text.len() == 1 && text.is_ascii() => Some(text.as_bytes()[0]),
+        _ => None,
+    }
+}
+
+/// Inverse of `parse_dialect_char`, for display.
+pub fn format_dialect_char(byte: u8) -> String {
+    match byte {
+        b'\t' => "\\t".to_string(),
+        _ => (byte as char).to_string(),
+    }
+}
+
+/// Sniffs the dialect of a CSV sample (typically the first `SNIFF_SAMPLE_BYTES` of a file).
+/// The sample is transcoded to UTF-8 with the detected encoding first.
+pub fn sniff(sample: &[u8]) -> Dialect {
+    let (encoding, bom) = encoding::detect(sample);
+    let truncated = sample.len() >= SNIFF_SAMPLE_BYTES;
+    let decoded = encoding::decode(sample, encoding);
+    let sample = complete_lines(&decoded, truncated);
+    let quote = detect_quote(sample);
+    let escape = detect_escape(sample, quote);
+
+    let mut dialect = Dialect {
+        quote,
+        escape,
+        encoding,
+        bom,
+        terminator: detect_terminator(sample),
+        ..Dialect::default()
+    };
+
+    let mut best_score = (0.0, 0);
+    for delimiter in DELIMITER_CANDIDATES {
+        let candidate = Dialect {
+            delimiter,
+            ..dialect
+        };
+        let counts: Vec<usize> = read_rows(sample, &candidate).iter().map(Vec::len).collect();
+        let score = consistency_score(&counts);
+        // Higher consistency wins; ties go to the delimiter that splits rows into more fields.
+        if score.1 > 1
+            && (score.0 > best_score.0 || (score.0 == best_score.0 && score.1 > best_score.1))
+        {
+            best_score = score;
+            dialect.delimiter = delimiter;
+        }
+    }
+
+    dialect.has_headers = detect_headers(&read_rows(sample, &dialect));
+    dialect.quoting = detect_quoting(sample, &dialect);
+    dialect
+}
+
+/// Drops a trailing partial line so a truncated sample doesn't skew the field counts.
+fn complete_lines(sample: &[u8], truncated: bool) -> &[u8] {
+    if !truncated {
+        return sample;
+    }
+    match sample.iter().rposition(|&b| b == b'\n') {
+        Some(end) => &sample[..=end],
+        None => sample,
+    }
+}
+
+/// Picks the quote character that most often opens a field.
+fn detect_quote(sample: &[u8]) -> u8 {
+    let mut best = (b'"', 0);
+    for quote in QUOTE_CANDIDATES {
+        let opens = sample
+            .iter()
+            .enumerate()
+            .filter(|&(i, &b)| {
+                b == quote
+                    && (i == 0
+                        || matches!(sample[i - 1], b'\n' | b'\r' | b' ')
+                        || DELIMITER_CANDIDATES.contains(&sample[i - 1]))
+            })
+            .count();
+        if opens > best.1 {
+            best = (quote, opens);
+        }
+    }
+    best.0
+}
+
+/// Backslash escaping is assumed only when `\"` is more common than `""`.
+fn detect_escape(sample: &[u8], quote: u8) -> Option<u8> {
+    let pairs = |first: u8| {
+        sample
+            .windows(2)
+            .filter(|w| w[0] == first && w[1] == quote)
+            .count()
+    };
+    let backslashed = pairs(b'\\');
+    (backslashed > 0 && backslashed > pairs(quote)).then_some(b'\\')
+}
+
+/// CRLF when most line breaks are CRLF.
+fn detect_terminator(sample: &[u8]) -> LineTerminator {
+    let lf = sample.iter().filter(|&&b| b == b'\n').count();
+    let crlf = sample.windows(2).filter(|w| w == b"\r\n").count();
+    if crlf > 0 && crlf * 2 >= lf {
+        LineTerminator::CrLf
+    } else {
+        LineTerminator::Lf
+    }
+}
+
+/// Classifies every non-empty field by whether it was quoted and whether it
+/// is numeric. All quoted means `Always`; exactly the non-numeric ones
+/// quoted means `NonNumeric`.
+fn detect_quoting(sample: &[u8], dialect: &Dialect) -> Quoting {
+    let is_number =
+        |s: &[u8]| std::str::from_utf8(s).is_ok_and(|s| s.trim().parse::<f64>().is_ok());
+    let (mut quoted_text, mut quoted_numbers, mut bare_text, mut bare_numbers) = (0, 0, 0, 0);
+    let mut i = 0;
+    while i < sample.len() {
+        let start = i;
+        let quoted = sample[i] == dialect.quote;
+        if quoted {
+            i += 1;
+            while i < sample.len() {
+                if Some(sample[i]) == dialect.escape {
+                    i += 2;
+                } else if sample[i] == dialect.quote {
+                    if sample.get(i + 1) == Some(&dialect.quote) && dialect.escape.is_none() {
+                        i += 2;
+                    } else {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        while i < sample.len()
+            && !matches!(sample[i], b'\n' | b'\r')
+            && sample[i] != dialect.delimiter
+        {
+            i += 1;
+        }
+        let field = &sample[start..i.min(sample.len())];
+        if quoted {
+            let inner = &field[1..field.len().saturating_sub(1).max(1)];
+            if is_number(inner) {
+                quoted_numbers += 1;
+            } else {
+                quoted_text += 1;
+            }
+        } else if !field.is_empty() {
+            if is_number(field) {
+                bare_numbers += 1;
+            } else {
+                bare_text += 1;
+            }
+        }
+        i += 1; // Past the delimiter or line break; `\r\n` leaves an empty field.
+    }
+
+    if quoted_text + quoted_numbers > 0 && bare_text + bare_numbers == 0 {
+        Quoting::Always
+    } else if quoted_text > 0 && bare_numbers > 0 && quoted_numbers == 0 && bare_text == 0 {
+        Quoting::NonNumeric
+    } else {
+        Quoting::Necessary
+    }
+}
+
+/// Parses `sample` with `dialect`, honouring quotes and multi-line fields.
+fn read_rows(sample: &[u8], dialect: &Dialect) -> Vec<Vec<String>> {
+    let mut rdr = dialect
+        .reader_builder()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(sample);
+    rdr.records()
+        .map_while(Result::ok)
+        .map(|r| r.iter().map(|s| s.to_string()).collect())
+        .collect()
+}
+
+/// Returns `(share of rows with the most common field count, that field count)`.
+fn consistency_score(counts: &[usize]) -> (f64, usize) {
+    let mut tally: Vec<(usize, usize)> = Vec::new();
+    for &count in counts {
+        match tally.iter_mut().find(|(c, _)| *c == count) {
+            Some((_, n)) => *n += 1,
+            None => tally.push((count, 1)),
+        }
+    }
+    tally
+        .into_iter()
+        .max_by_key(|&(count, n)| (n, count))
+        .map(|(count, n)| (n as f64 / counts.len() as f64, count))
+        .unwrap_or((0.0, 0))
+}
+
+/// Type heuristic: a header row is text where the rows below it are numeric.
+/// For all-text columns, a first-row value that repeats further down, or an
+/// empty first-row cell, counts as data. Without evidence either way, assumes
+/// a header is present.
+fn detect_headers(rows: &[Vec<String>]) -> bool {
+    let Some((first, rest)) = rows.split_first() else {
+        return true;
+    };
+    if rest.is_empty() {
+        return true;
+    }
+
+    let is_number = |s: &str| s.trim().parse::<f64>().is_ok();
+    let (mut header_votes, mut data_votes) = (0, 0);
+    for (column, value) in first.iter().enumerate() {
+        let values: Vec<&str> = rest
+            .iter()
+            .filter_map(|r| r.get(column))
+            .map(|s| s.as_str())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let numeric = values.iter().filter(|s| is_number(s)).count();
+        if value.is_empty() {
+            data_votes += 1;
+            continue; // Header cells are rarely blank.
+        }
+        if values.is_empty() || numeric * 2 <= values.len() {
+            if values.contains(&value.as_str()) {
+                data_votes += 1; // A header name would not repeat as a value.
+            }
+            continue;
+        }
+        if is_number(value) {
+            data_votes += 1;
+        } else {
+            header_votes += 1;
+        }
+    }
+    data_votes <= header_votes
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_delimiters_inside_quotes() {
+        let sample = b"\"Last, First\";Age\n\"Doe, John\";25\n\"Smith, Alice\";22\n";
+
+        let dialect = sniff(sample);
+
+        assert_eq!(dialect.delimiter, b';');
+        assert_eq!(dialect.quote, b'"');
+    }
+
+    #[test]
+    fn scores_delimiters_by_consistency_across_rows() {
+        // The first line alone has more commas than pipes.
+        let sample = b"a,b,c|d\n1|2\n3|4\n5|6\n";
+
+        assert_eq!(sniff(sample).delimiter, b'|');
+    }
+
+    #[test]
+    fn detects_single_quotes_and_backslash_escapes() {
+        let sample = b"name,quote\n'Bob','It\\'s fine'\n'Ann','Say \\'hi\\''\n";
+
+        let dialect = sniff(sample);
+
+        assert_eq!(dialect.quote, b'\'');
+        assert_eq!(dialect.escape, Some(b'\\'));
+    }
+
+    #[test]
+    fn detects_missing_header_row() {
+        assert!(sniff(b"name,age\nAlice,30\nBob,25\n").has_headers);
+        assert!(!sniff(b"Alice,30\nBob,25\nCarol,41\n").has_headers);
+    }
+
+    #[test]
+    fn detects_missing_header_row_in_text_only_files() {
+        assert!(sniff(b"city,country\nOslo,Norway\nBergen,Norway\n").has_headers);
+        assert!(!sniff(b"Oslo,Norway\nBergen,Norway\nLyon,France\n").has_headers);
+        assert!(!sniff(b"Oslo,\nBergen,Norway\nLyon,France\n").has_headers);
+    }
+
+    #[test]
+    fn detects_line_terminator_and_quoting_style() {
+        let plain = sniff(b"name,age\nAlice,30\nBob,25\n");
+        assert_eq!(plain.terminator, LineTerminator::Lf);
+        assert_eq!(plain.quoting, Quoting::Necessary);
+
+        let always = sniff(b"\"name\",\"age\"\r\n\"Alice\",\"30\"\r\n\"Bob \"\"B\"\"\",\"25\"\r\n");
+        assert_eq!(always.terminator, LineTerminator::CrLf);
+        assert_eq!(always.quoting, Quoting::Always);
+
+        let non_numeric = sniff(b"\"name\";\"age\"\n\"Alice\";30\n\"Bob\";25\n");
+        assert_eq!(non_numeric.quoting, Quoting::NonNumeric);
+
+        let mixed = sniff(b"name,note\nAlice,\"a, b\"\nBob,plain\n");
+        assert_eq!(mixed.quoting, Quoting::Necessary);
+    }
+
+    #[test]
+    fn parses_and_formats_dialect_chars() {
+        assert_eq!(parse_dialect_char(";"), Some(b';'));
+        assert_eq!(parse_dialect_char("\\t"), Some(b'\t'));
+        assert_eq!(parse_dialect_char("tab"), Some(b'\t'));
+        assert_eq!(parse_dialect_char(""), None);
+        assert_eq!(parse_dialect_char(";;"), None);
+        assert_eq!(format_dialect_char(b'\t'), "\\t");
+        assert_eq!(format_dialect_char(b'|'), "|");
+    }
+
+    #[test]
+    fn falls_back_to_defaults_for_single_column_data() {
+        assert_eq!(sniff(b"value\n1\n2\n"), Dialect::default());
+        assert_eq!(sniff(b""), Dialect::default());
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Text encoding detection and transcoding: everything downstream of the
+// loaders sees UTF-8, and exports can be encoded back to the source encoding.
+
+use encoding_rs::{Encoder, EncoderResult, Encoding, UTF_8, UTF_16BE, UTF_16LE};
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
+
+/// Works out the encoding of a sample and whether it starts with a BOM. A BOM
+/// wins, then the NUL pattern of BOM-less UTF-16, then valid UTF-8, then
+/// chardetng's guess among the legacy encodings.
+pub fn detect(sample: &[u8]) -> (&'static Encoding, bool) {
+    if let Some((encoding, _)) = Encoding::for_bom(sample) {
+        return (encoding, true);
+    }
+    // Checked before UTF-8: ASCII text in UTF-16 is also valid UTF-8.
+    if let Some(encoding) = detect_utf16(sample) {
+        return (encoding, false);
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => return (UTF_8, false),
+        // A multi-byte character cut off by the end of the sample is still UTF-8.
+        Err(e) if e.error_len().is_none() => return (UTF_8, false),
+        Err(_) => {}
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(sample, true);
+    (detector.guess(None, false), false)
+}
+
+/// Mostly-ASCII UTF-16 has a NUL in every other byte.
+fn detect_utf16(sample: &[u8]) -> Option<&'static Encoding> {
+    let pairs = sample.len().min(4096) / 2;
+    if pairs == 0 {
+        return None;
+    }
+    let nuls_at = |offset: usize| (0..pairs).filter(|&i| sample[2 * i + offset] == 0).count();
+    let (even, odd) = (nuls_at(0), nuls_at(1));
+    if odd * 10 >= pairs * 9 && even * 10 < pairs {
+        Some(UTF_16LE)
+    } else if even * 10 >= pairs * 9 && odd * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// Transcodes `bytes` to UTF-8 and drops a leading BOM. UTF-8 input is only
+/// borrowed, and left unvalidated so the CSV reader can report bad bytes by
+/// position instead of replacing them.
+pub fn decode<'a>(bytes: &'a [u8], encoding: &'static Encoding) -> Cow<'a, [u8]> {
+    if encoding == UTF_8 {
+        return Cow::Borrowed(bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes));
+    }
+    let (text, _) = encoding.decode_with_bom_removal(bytes);
+    Cow::Owned(text.into_owned().into_bytes())
+}
+
+/// Streaming `decode`, for the loaders that never hold the whole file.
+pub fn decode_reader<R: Read>(
+    reader: R,
+    encoding: &'static Encoding,
+) -> DecodeReaderBytes<R, Vec<u8>> {
+    let mut builder = DecodeReaderBytesBuilder::new();
+    builder.strip_bom(true);
+    if encoding == UTF_8 {
+        builder.bom_sniffing(false);
+    } else {
+        builder.encoding(Some(encoding));
+    }
+    builder.build(reader)
+}
+
+/// Encodes UTF-8 `text` as `encoding`, optionally prefixed with its BOM
+/// (only UTF-8 and UTF-16 have one). Fails on characters the target encoding
+/// cannot represent rather than substituting them.
+pub fn encode(text: &[u8], encoding: &'static Encoding, bom: bool) -> io::Result<Vec<u8>> {
+    if encoding == UTF_8 && !bom {
+        return Ok(text.to_vec());
+    }
+    let mut writer = EncodeWriter::new(Vec::with_capacity(text.len() + 3), encoding, bom);
+    writer.write_all(text)?;
+    writer.finish()
+}
+
+/// Streaming `encode`: UTF-8 written to it comes out of `inner` in `encoding`.
+/// A character split across two writes is held back until it is complete.
+pub struct EncodeWriter<W: Write> {
+    inner: W,
+    encoding: &'static Encoding,
+    encoder: Encoder,
+    bom_pending: bool,
+    /// The start of a multi-byte character cut off at the end of the last write.
+    partial: Vec<u8>,
+    /// Reused output buffer for legacy encodings.
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    pub fn new(inner: W, encoding: &'static Encoding, bom: bool) -> Self {
+        Self {
+            inner,
+            encoding,
+            encoder: encoding.new_encoder(),
+            bom_pending: bom && (encoding == UTF_8 || encoding == UTF_16LE || encoding == UTF_16BE),
+            partial: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Writes any pending BOM and returns the underlying writer. Fails if the
+    /// input ended in the middle of a character.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_bom()?;
+        if !self.partial.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "text ends in the middle of a UTF-8 character",
+            ));
+        }
+        // Lets stateful encodings such as ISO-2022-JP return to their initial state.
+        self.buffer.clear();
+        self.buffer.reserve(16);
+        let _ =
+            self.encoder
+                .encode_from_utf8_to_vec_without_replacement("", &mut self.buffer, true);
+        self.inner.write_all(&self.buffer)?;
+        Ok(self.inner)
+    }
+
+    fn write_bom(&mut self) -> io::Result<()> {
+        if std::mem::take(&mut self.bom_pending) {
+            let bom: &[u8] = match self.encoding {
+                e if e == UTF_16LE => b"\xFF\xFE",
+                e if e == UTF_16BE => b"\xFE\xFF",
+                _ => b"\xEF\xBB\xBF",
+            };
+            self.inner.write_all(bom)?;
+        }
+        Ok(())
+    }
+
+    fn encode_str(&mut self, text: &str) -> io::Result<()> {
+        if self.encoding == UTF_8 {
+            return self.inner.write_all(text.as_bytes());
+        }
+        self.buffer.clear();
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            for unit in text.encode_utf16() {
+                self.buffer
+                    .extend_from_slice(&if self.encoding == UTF_16LE {
+                        unit.to_le_bytes()
+                    } else {
+                        unit.to_be_bytes()
+                    });
+            }
+            return self.inner.write_all(&self.buffer);
+        }
+
+        let mut remaining = text;
+        loop {
+            self.buffer.reserve(remaining.len() + 16);
+            let (result, read) = self.encoder.encode_from_utf8_to_vec_without_replacement(
+                remaining,
+                &mut self.buffer,
+                false,
+            );
+            remaining = &remaining[read..];
+            match result {
+                EncoderResult::InputEmpty => return self.inner.write_all(&self.buffer),
+                EncoderResult::OutputFull => {}
+                EncoderResult::Unmappable(c) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{:?} cannot be encoded as {}", c, self.encoding.name()),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_bom()?;
+        if self.encoding == UTF_8 {
+            // Passed through unvalidated, as `encode` does.
+            self.inner.write_all(buf)?;
+            return Ok(buf.len());
+        }
+        let joined;
+        let bytes = if self.partial.is_empty() {
+            buf
+        } else {
+            joined = [std::mem::take(&mut self.partial).as_slice(), buf].concat();
+            &joined[..]
+        };
+        let valid = match std::str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) if e.error_len().is_none() => {
+                self.partial = bytes[e.valid_up_to()..].to_vec();
+                std::str::from_utf8(&bytes[..e.valid_up_to()]).expect("checked above")
+            }
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        self.encode_str(valid)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Looks up an encoding by any of its WHATWG labels (`latin1`, `utf-16le`, ...).
+pub fn parse_encoding(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::WINDOWS_1252;
+
+    #[test]
+    fn detects_boms_utf8_and_legacy_encodings() {
+        assert_eq!(detect(b"\xEF\xBB\xBFa,b\n"), (UTF_8, true));
+        assert_eq!(detect(b"\xFF\xFEa\0,\0b\0"), (UTF_16LE, true));
+        assert_eq!(
+            detect("name,city\nJosé,Málaga\n".as_bytes()),
+            (UTF_8, false)
+        );
+        assert_eq!(detect(b"a\0,\0b\0\n\0"), (UTF_16LE, false));
+        assert_eq!(
+            detect(b"name,city\nJos\xe9,M\xe1laga\nFran\xe7ois,Orl\xe9ans\n"),
+            (WINDOWS_1252, false)
+        );
+    }
+
+    #[test]
+    fn round_trips_through_legacy_and_utf16_encodings() {
+        let text = "name\nJosé\n".as_bytes();
+        for (encoding, bom) in [(WINDOWS_1252, false), (UTF_16LE, true), (UTF_16BE, false)] {
+            let encoded = encode(text, encoding, bom).unwrap();
+            assert_eq!(decode(&encoded, encoding).as_ref(), text);
+        }
+        assert_eq!(encode(text, UTF_8, true).unwrap()[..3], *b"\xEF\xBB\xBF");
+    }
+
+    #[test]
+    fn encodes_characters_split_across_writes() {
+        let text = "José ☃\n".as_bytes();
+        let mut writer = EncodeWriter::new(Vec::new(), UTF_16LE, false);
+        for byte in text {
+            writer.write_all(std::slice::from_ref(byte)).unwrap();
+        }
+        let encoded = writer.finish().unwrap();
+        assert_eq!(decode(&encoded, UTF_16LE).as_ref(), text);
+    }
+
+    #[test]
+    fn refuses_to_encode_unmappable_characters() {
+        assert!(encode("snow ☃".as_bytes(), WINDOWS_1252, false).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Environment metadata: which build ran a benchmark and on what machine, so a
+// timing like "Quick Sort: 12.3 ms" can be interpreted later.
+
+use crate::bench;
+use crate::memory;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// The build and machine a run was measured on. Fields that could not be
+/// determined are empty (or 0).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Environment {
+    pub crate_version: String,
+    /// Short commit hash the binary was built from.
+    pub git_commit: String,
+    /// `debug` or `release`.
+    pub profile: String,
+    /// e.g. `rustc 1.85.0 (4d91de4e4 2025-02-17)`.
+    pub rustc: String,
+    pub cpu_model: String,
+    /// Logical cores.
+    pub cpu_cores: usize,
+    pub memory_bytes: u64,
+    /// `std::env::consts::OS` and `ARCH`, e.g. `linux x86_64`.
+    pub os: String,
+    pub kernel: String,
+    /// Local time the run started, RFC 3339.
+    pub timestamp: String,
+}
+
+impl Environment {
+    /// Captures the current build and machine. Linux details come from
+    /// `/proc`; elsewhere those fields stay empty.
+    pub fn capture() -> Self {
+        let (cpu_model, cpu_cores) = fs::read_to_string("/proc/cpuinfo")
+            .map(|text| parse_cpuinfo(&text))
+            .unwrap_or_default();
+        let cpu_cores = match cpu_cores {
+            0 => std::thread::available_parallelism().map_or(0, |n| n.get()),
+            cores => cores,
+        };
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("BUILD_GIT_COMMIT").to_string(),
+            profile: if bench::DEBUG_BUILD {
+                "debug"
+            } else {
+                "release"
+            }
+            .to_string(),
+            rustc: env!("BUILD_RUSTC_VERSION").to_string(),
+            cpu_model,
+            cpu_cores,
+            memory_bytes: fs::read_to_string("/proc/meminfo")
+                .ok()
+                .and_then(|text| parse_meminfo(&text))
+                .unwrap_or(0),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            kernel: fs::read_to_string("/proc/sys/kernel/osrelease")
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default(),
+            timestamp: chrono::Local::now().to_rfc3339(),
+        }
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self.profile == "debug"
+    }
+
+    /// Human-readable label/value pairs, for the GUI and Markdown reports.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let or_unknown = |value: &str| match value {
+            "" => "unknown".to_string(),
+            value => value.to_string(),
+        };
+        vec![
+            ("Version", or_unknown(&self.crate_version)),
+            ("Commit", or_unknown(&self.git_commit)),
+            ("Profile", or_unknown(&self.profile)),
+            ("Compiler", or_unknown(&self.rustc)),
+            ("CPU", or_unknown(&self.cpu_model)),
+            ("Cores", self.cpu_cores.to_string()),
+            (
+                "Memory",
+                match self.memory_bytes {
+                    0 => "unknown".to_string(),
+                    bytes => memory::format_bytes(bytes),
+                },
+            ),
+            ("OS", or_unknown(&self.os)),
+            ("Kernel", or_unknown(&self.kernel)),
+            ("Time", or_unknown(&self.timestamp)),
+        ]
+    }
+}
+
+/// The first `model name` and the number of `processor` entries.
+fn parse_cpuinfo(text: &str) -> (String, usize) {
+    let model = text
+        .lines()
+        .filter(|line| line.starts_with("model name"))
+        .find_map(|line| line.split_once(':'))
+        .map(|(_, value)| value.trim().to_string())
+        .unwrap_or_default();
+    let cores = text
+        .lines()
+        .filter(|line| line.split(':').next().map(str::trim) == Some("processor"))
+        .count();
+    (model, cores)
+}
+
+/// `MemTotal`, which `/proc/meminfo` gives in KiB.
+fn parse_meminfo(text: &str) -> Option<u64> {
+    let line = text.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cpu_and_memory_from_proc() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\n\
+                       model name\t: Intel(R) Xeon(R) CPU @ 2.20GHz\n\n\
+                       processor\t: 1\nmodel name\t: Intel(R) Xeon(R) CPU @ 2.20GHz\n";
+        let meminfo = "MemTotal:       16314736 kB\nMemFree:         1234 kB\n";
+
+        assert_eq!(
+            parse_cpuinfo(cpuinfo),
+            ("Intel(R) Xeon(R) CPU @ 2.20GHz".to_string(), 2)
+        );
+        assert_eq!(parse_cpuinfo(""), (String::new(), 0));
+        assert_eq!(parse_meminfo(meminfo), Some(16314736 * 1024));
+        assert_eq!(parse_meminfo("MemFree: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn captures_the_build_and_host() {
+        let env = Environment::capture();
+
+        assert_eq!(env.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(env.profile == "debug", cfg!(debug_assertions));
+        assert!(env.cpu_cores > 0);
+        assert!(env.os.starts_with(std::env::consts::OS));
+        assert!(chrono::DateTime::parse_from_rfc3339(&env.timestamp).is_ok());
+    }
+}
@@ -1,56 +1,211 @@
 // Copyright (c) 2026 Neil Pandya
 
-use crate::algorithms;
-use crate::io;
+use crate::arena::{self, RecordArena};
+use crate::bench::{self, BUBBLE_SORT_ROW_LIMIT, BenchOptions};
+use crate::compression::Compression;
+use crate::diagnostics::RowIssue;
+use crate::dialect::{
+    DELIMITER_CANDIDATES, ExportOptions, LineTerminator, LoadOptions, Quoting, format_dialect_char,
+};
+use crate::environment::Environment;
+use crate::gui::history_panel::HistoryPanel;
+use crate::gui::load_dialog::{LoadDialog, LoadDialogAction};
+use crate::gui::suite_panel::SuitePanel;
+use crate::io::{self, LoadReport, TableReader};
+use crate::memory;
 use crate::models::{BenchResult, CsvError, Record};
+use crate::pipeline::{self, PipelineTimings};
+use crate::readers::{InputFormat, NdjsonReader, ParquetReader};
+use crate::report::{BenchParams, BenchReport, DatasetInfo, ReportFormat};
+use crate::writers::OutputFormat;
 use eframe::egui;
 use egui_plot::{Bar, BarChart, Plot};
 use std::path::PathBuf;
 
-const BUBBLE_SORT_ROW_LIMIT: usize = 1000; // Bubble/insertion sort gets slow beyond this
-
+#[derive(Default)]
 pub struct SortBenchApp {
     records: Vec<Record>,
     headers: Vec<String>,
     results: Vec<BenchResult>,
+    /// The last run with its dataset and parameters, for "Export Results".
+    report: Option<BenchReport>,
+    /// Past runs, each recorded when it finishes.
+    history: HistoryPanel,
+    /// The last benchmark suite run from a TOML file.
+    suite: SuitePanel,
     selected_column_index: usize,
     loaded_file_path: Option<PathBuf>,
+    /// Format of the loaded file; only CSV supports the arena and mmap loaders.
+    input_format: InputFormat,
+    /// Options the current file was loaded with; reused for the arena and load-path comparisons.
+    load_options: LoadOptions,
+    /// Open while the user reviews the dialect of a freshly picked file.
+    load_dialog: Option<LoadDialog>,
+    /// Rows the last load padded, truncated, kept ragged or skipped.
+    row_issues: Vec<RowIssue>,
     error: Option<CsvError>,
+    /// Also benchmark each algorithm sorting row indices instead of whole rows.
+    compare_index_sort: bool,
+    /// Also benchmark each algorithm on arena-backed storage.
+    compare_arena_storage: bool,
+    /// Arena copy of the loaded file, built on demand when `compare_arena_storage` is set.
+    arena: Option<RecordArena>,
+    /// Timings from the last "Compare Load Paths" run.
+    load_reports: Vec<LoadReport>,
+    /// Per-stage timings of the last load, benchmark run and export.
+    timings: PipelineTimings,
+    /// Dialect for the next export: the loaded file's, plus any overrides.
+    export_options: ExportOptions,
+    /// File format of the next export.
+    export_format: OutputFormat,
+    /// Write exports in the source file's encoding instead of UTF-8.
+    export_original_encoding: bool,
+    /// Count heap allocations during each algorithm's run.
+    track_allocations: bool,
+    /// Which measurement the results chart plots.
+    chart_metric: ChartMetric,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ChartMetric {
+    #[default]
+    Time,
+    BytesAllocated,
+    Allocations,
+    PeakLiveBytes,
 }
 
-impl Default for SortBenchApp {
-    fn default() -> Self {
-        Self {
-            records: Vec::new(),
-            headers: Vec::new(),
-            results: Vec::new(),
-            selected_column_index: 0,
-            loaded_file_path: None,
-            error: None,
+impl ChartMetric {
+    const ALL: [ChartMetric; 4] = [
+        ChartMetric::Time,
+        ChartMetric::BytesAllocated,
+        ChartMetric::Allocations,
+        ChartMetric::PeakLiveBytes,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ChartMetric::Time => "Results (ms)",
+            ChartMetric::BytesAllocated => "Bytes allocated",
+            ChartMetric::Allocations => "Allocation count",
+            ChartMetric::PeakLiveBytes => "Peak live bytes",
+        }
+    }
+
+    /// The value to plot for `result`; allocation metrics are zero when untracked.
+    fn value(&self, result: &BenchResult) -> f64 {
+        let stats = result.alloc_stats.unwrap_or_default();
+        match self {
+            ChartMetric::Time => result.duration_ms,
+            ChartMetric::BytesAllocated => stats.bytes_allocated as f64,
+            ChartMetric::Allocations => stats.allocations as f64,
+            ChartMetric::PeakLiveBytes => stats.peak_live_bytes as f64,
         }
     }
 }
 
 impl eframe::App for SortBenchApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if let Some(dialog) = &mut self.load_dialog {
+            match dialog.show(ctx) {
+                Some(LoadDialogAction::Load(path, options)) => {
+                    self.load_dialog = None;
+                    self.load_csv(path, options);
+                }
+                Some(LoadDialogAction::LoadFixedWidth(path, reader)) => {
+                    self.load_dialog = None;
+                    self.load_table(
+                        path,
+                        InputFormat::FixedWidth,
+                        &reader,
+                        LoadOptions::default(),
+                    );
+                }
+                Some(LoadDialogAction::Cancel) => self.load_dialog = None,
+                None => {}
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("CSV Sorting Benchmarker");
 
+            if bench::DEBUG_BUILD {
+                egui::Frame::none()
+                    .fill(egui::Color32::DARK_RED)
+                    .inner_margin(6.0)
+                    .show(ui, |ui| {
+                        ui.colored_label(
+                            egui::Color32::WHITE,
+                            format!("⚠️ {}", bench::DEBUG_BUILD_WARNING),
+                        );
+                    });
+            }
+
             ui.horizontal(|ui| {
-                if ui.button("Upload CSV File").clicked() {
-                    if let Some(path) = rfd::FileDialog::new()
-                        .add_filter("CSV", &["csv"])
+                if ui.button("Upload CSV File").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("CSV", &["csv", "tsv", "txt"])
+                        .add_filter("Compressed CSV", &["gz", "zst", "bz2"])
+                        .add_filter("JSON Lines", &["jsonl", "ndjson"])
+                        .add_filter("Parquet", &["parquet"])
                         .pick_file()
-                    {
-                        self.load_csv(path);
+                {
+                    // Self-describing formats skip the dialect review.
+                    match InputFormat::from_path(&path) {
+                        InputFormat::Ndjson => self.load_table(
+                            path,
+                            InputFormat::Ndjson,
+                            &NdjsonReader,
+                            LoadOptions::default(),
+                        ),
+                        InputFormat::Parquet => self.load_table(
+                            path,
+                            InputFormat::Parquet,
+                            &ParquetReader,
+                            LoadOptions::default(),
+                        ),
+                        _ => self.load_dialog = Some(LoadDialog::new(path)),
                     }
                 }
 
-                if !self.records.is_empty() && ui.button("Export Sorted CSV").clicked() {
+                if let Err(e) = self.suite.poll() {
+                    self.error = Some(e);
+                }
+                if ui
+                    .add_enabled(!self.suite.is_running(), egui::Button::new("Run Suite"))
+                    .clicked()
+                    && let Err(e) = self.suite.open_and_run(ctx)
+                {
+                    self.error = Some(e);
+                }
+                self.suite.show_progress(ui);
+
+                if !self.records.is_empty() && ui.button("Export Sorted Data").clicked() {
                     self.export_csv();
                 }
+
+                if self.loaded_file_path.is_some()
+                    && self.input_format == InputFormat::Csv
+                    && ui.button("Compare Load Paths").clicked()
+                {
+                    self.compare_load_paths();
+                }
             });
 
+            for report in &self.load_reports {
+                let memory = report
+                    .peak_memory_bytes
+                    .map(memory::format_bytes)
+                    .unwrap_or_else(|| "n/a (Linux only)".to_string());
+                ui.label(format!(
+                    "{}: {} rows in {:.2} ms, peak memory +{}",
+                    report.method.name(),
+                    report.rows,
+                    report.duration_ms,
+                    memory
+                ));
+            }
+
             if !self.records.is_empty() && self.records.len() > BUBBLE_SORT_ROW_LIMIT {
                 ui.colored_label(
                     egui::Color32::YELLOW,
@@ -61,6 +216,38 @@ impl eframe::App for SortBenchApp {
                 );
             }
 
+            if !self.row_issues.is_empty() {
+                egui::CollapsingHeader::new(format!(
+                    "⚠️ {} ragged or unreadable row(s) ({})",
+                    self.row_issues.len(),
+                    self.load_options.ragged_rows.description()
+                ))
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(120.0)
+                        .show(ui, |ui| {
+                            for issue in &self.row_issues {
+                                ui.label(issue.to_string());
+                            }
+                        });
+                });
+            }
+
+            if self.loaded_file_path.is_some() {
+                match self.input_format {
+                    InputFormat::Csv => {
+                        ui.label(format!("Dialect: {}", self.load_options.dialect))
+                    }
+                    format => ui.label(format!("Format: {}", format.name())),
+                };
+                egui::CollapsingHeader::new("Export options")
+                    .show(ui, |ui| self.show_export_options(ui));
+            }
+
+            if !self.headers.is_empty() && !self.load_options.dialect.has_headers {
+                ui.label("No header row: columns are named column_1..column_n and exports omit the header.");
+            }
+
             if !self.headers.is_empty() {
                 ui.horizontal(|ui| {
                     ui.label("Sort by Column:");
@@ -74,19 +261,57 @@ impl eframe::App for SortBenchApp {
                 });
             }
 
+            ui.checkbox(
+                &mut self.compare_index_sort,
+                "Compare index sort (sort row indices, permute once)",
+            );
+            ui.checkbox(
+                &mut self.compare_arena_storage,
+                "Compare arena storage (one buffer + offsets)",
+            );
+            ui.checkbox(
+                &mut self.track_allocations,
+                "Track allocations (bytes, count, peak live bytes)",
+            );
+
+            if let Some(arena) = &self.arena {
+                ui.label(format!(
+                    "Memory: Vec<Vec<String>> {:.1} KiB, arena {:.1} KiB",
+                    arena::records_heap_size(&self.records) as f64 / 1024.0,
+                    arena.heap_size() as f64 / 1024.0,
+                ));
+            }
+
             ui.add_space(10.0);
             if ui.button("Run Benchmarks").clicked() && !self.records.is_empty() {
                 self.run_benchmarks();
             }
 
+            if self.loaded_file_path.is_some() {
+                ui.label(format!("Pipeline (ms): {}", self.timings));
+            }
+
             if !self.results.is_empty() {
                 ui.add_space(20.0);
-                ui.label("Results (ms):");
+                ui.horizontal(|ui| {
+                    ui.label("Chart:");
+                    egui::ComboBox::from_id_salt("chart_metric")
+                        .selected_text(self.chart_metric.label())
+                        .show_ui(ui, |ui| {
+                            for metric in ChartMetric::ALL {
+                                ui.selectable_value(&mut self.chart_metric, metric, metric.label());
+                            }
+                        });
+                    if ui.button("Export Results").clicked() {
+                        self.export_results();
+                    }
+                });
+                let metric = self.chart_metric;
                 let bars: Vec<Bar> = self
                     .results
                     .iter()
                     .enumerate()
-                    .map(|(i, res)| Bar::new(i as f64, res.duration_ms).name(&res.name))
+                    .map(|(i, res)| Bar::new(i as f64, metric.value(res)).name(&res.name))
                     .collect();
 
                 Plot::new("Benchmark Results")
@@ -94,8 +319,27 @@ impl eframe::App for SortBenchApp {
                     .show(ui, |plot_ui| {
                         plot_ui.bar_chart(BarChart::new(bars).width(0.5));
                     });
+
+                if let Some(report) = &self.report {
+                    egui::CollapsingHeader::new("Environment").show(ui, |ui| {
+                        egui::Grid::new("environment").striped(true).show(ui, |ui| {
+                            for (label, value) in report.environment.fields() {
+                                ui.strong(label);
+                                ui.label(value);
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
             }
 
+            egui::CollapsingHeader::new("History").show(ui, |ui| self.history.show(ui));
+            egui::CollapsingHeader::new("Suite").show(ui, |ui| {
+                if let Err(e) = self.suite.show(ui) {
+                    self.error = Some(e);
+                }
+            });
+
             ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
                 if ui.button("Exit").clicked() {
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
@@ -106,40 +350,200 @@ impl eframe::App for SortBenchApp {
 }
 
 impl SortBenchApp {
-    fn load_csv(&mut self, path: PathBuf) {
-        match io::load_csv(&path) {
-            Ok((headers, records)) => {
-                self.headers = headers;
-                self.records = records;
+    /// Starts empty, with the run history read from the user data directory.
+    pub fn new() -> Self {
+        let mut app = Self::default();
+        match HistoryPanel::open() {
+            Ok(history) => app.history = history,
+            Err(e) => app.error = Some(e),
+        }
+        app
+    }
+
+    fn show_export_options(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Format")
+            .selected_text(self.export_format.description())
+            .show_ui(ui, |ui| {
+                for format in OutputFormat::ALL {
+                    ui.selectable_value(&mut self.export_format, format, format.description());
+                }
+            });
+        let options = &mut self.export_options;
+        ui.checkbox(
+            &mut options.backup,
+            "Keep a backup (.bak) when overwriting a file",
+        );
+        // The rest is CSV dialect; the other formats are always UTF-8.
+        if self.export_format != OutputFormat::Csv {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label("Delimiter:");
+            egui::ComboBox::from_id_salt("export_delimiter")
+                .selected_text(format_dialect_char(options.delimiter))
+                .show_ui(ui, |ui| {
+                    for delimiter in DELIMITER_CANDIDATES {
+                        ui.selectable_value(
+                            &mut options.delimiter,
+                            delimiter,
+                            format_dialect_char(delimiter),
+                        );
+                    }
+                });
+            ui.label("Quoting:");
+            egui::ComboBox::from_id_salt("export_quoting")
+                .selected_text(options.quoting.name())
+                .show_ui(ui, |ui| {
+                    for quoting in Quoting::ALL {
+                        ui.selectable_value(&mut options.quoting, quoting, quoting.name());
+                    }
+                });
+            ui.label("Line endings:");
+            egui::ComboBox::from_id_salt("export_terminator")
+                .selected_text(options.terminator.name().to_uppercase())
+                .show_ui(ui, |ui| {
+                    for terminator in LineTerminator::ALL {
+                        ui.selectable_value(
+                            &mut options.terminator,
+                            terminator,
+                            terminator.name().to_uppercase(),
+                        );
+                    }
+                });
+        });
+        ui.checkbox(&mut options.bom, "Write a byte-order mark");
+        let encoding = self.load_options.dialect.encoding;
+        if encoding != encoding_rs::UTF_8 {
+            ui.checkbox(
+                &mut self.export_original_encoding,
+                format!("Export in the original encoding ({})", encoding.name()),
+            );
+        }
+        if ui.button("Reset to source dialect").clicked() {
+            *options = ExportOptions::from(&self.load_options);
+        }
+    }
+
+    fn load_csv(&mut self, path: PathBuf, options: LoadOptions) {
+        self.load_table(path, InputFormat::Csv, &options, options);
+    }
+
+    /// Loads `path` through `reader`; `options` is the dialect exports start from.
+    fn load_table(
+        &mut self,
+        path: PathBuf,
+        format: InputFormat,
+        reader: &dyn TableReader,
+        options: LoadOptions,
+    ) {
+        match io::load_table_timed(&path, reader) {
+            Ok(loaded) => {
+                self.input_format = format;
+                self.row_issues = loaded.issues;
+                self.export_options = ExportOptions::from(&options);
+                self.load_options = options;
+                self.timings = loaded.timings;
+                self.headers = loaded.headers;
+                self.records = loaded.rows;
                 self.results.clear();
+                self.report = None;
+                self.arena = None;
+                self.load_reports.clear();
                 self.selected_column_index = 0;
                 self.loaded_file_path = Some(path);
                 self.error = None;
             }
             Err(e) => {
-                eprintln!("Failed to load CSV: {}", e);
-                self.error = Some(CsvError::ParseError(e.to_string()));
+                eprintln!("Failed to load {}: {}", format.name(), e);
+                self.error = Some(e);
             }
         }
     }
 
+    fn load_arena(&mut self) {
+        if self.input_format != InputFormat::Csv {
+            self.arena = Some(RecordArena::from_records(&self.records));
+        } else if let Some(path) = &self.loaded_file_path {
+            match io::load_csv_arena(path, &self.load_options) {
+                Ok(loaded) => self.arena = Some(loaded.rows),
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    fn compare_load_paths(&mut self) {
+        let Some(path) = &self.loaded_file_path else {
+            return;
+        };
+        let reports = io::LoadMethod::ALL
+            .iter()
+            .map(|&method| io::measure_load(path, method, &self.load_options))
+            .collect::<Result<Vec<_>, _>>();
+        match reports {
+            Ok(reports) => self.load_reports = reports,
+            Err(e) => self.error = Some(e),
+        }
+    }
+
     fn export_csv(&mut self) {
         if let Some(ref original_path) = self.loaded_file_path {
-            algorithms::standardsort::sort(&mut self.records, self.selected_column_index);
+            pipeline::sort_with_keys(
+                &mut self.records,
+                self.selected_column_index,
+                &mut self.timings,
+            );
 
-            let file_stem = original_path
+            // `data.csv.gz` suggests `data_sorted_by_<column>.csv.gz`.
+            let source_compression = Compression::from_path(original_path);
+            let uncompressed_path = match source_compression {
+                Compression::None => original_path.clone(),
+                _ => original_path.with_extension(""),
+            };
+            let file_stem = uncompressed_path
                 .file_stem()
                 .unwrap_or_default()
                 .to_string_lossy();
             let column_name = &self.headers[self.selected_column_index];
-            let suggested_name = format!("{}_sorted_by_{}.csv", file_stem, column_name);
-
-            if let Some(save_path) = rfd::FileDialog::new()
-                .set_file_name(&suggested_name)
-                .add_filter("CSV", &["csv", "tsv", "txt"])
-                .save_file()
+            let extension = match self.export_format {
+                OutputFormat::Csv if self.export_options.delimiter == b'\t' => "tsv",
+                format => format.extension(),
+            };
+            let mut suggested_name =
+                format!("{}_sorted_by_{}.{}", file_stem, column_name, extension);
+            // A SQLite database is a single file; it is never compressed.
+            if let Some(extension) = source_compression.extension()
+                && self.export_format != OutputFormat::Sqlite
             {
-                match io::save_csv(&save_path, &self.headers, &self.records) {
+                suggested_name = format!("{}.{}", suggested_name, extension);
+            }
+
+            let dialog = match self.export_format {
+                OutputFormat::Csv => {
+                    rfd::FileDialog::new().add_filter("CSV", &["csv", "tsv", "txt"])
+                }
+                format => {
+                    rfd::FileDialog::new().add_filter(format.description(), &[format.extension()])
+                }
+            };
+            let dialog = match self.export_format {
+                OutputFormat::Sqlite => dialog,
+                _ => dialog.add_filter("Compressed", &["gz", "zst", "bz2"]),
+            };
+            if let Some(save_path) = dialog.set_file_name(&suggested_name).save_file() {
+                let mut options = self.export_options;
+                options.compression = Compression::from_path(&save_path);
+                if self.export_original_encoding {
+                    options = options.keep_encoding(&self.load_options.dialect);
+                }
+                let writer = self.export_format.writer(&options);
+                match io::save_table(
+                    &save_path,
+                    writer.as_ref(),
+                    &self.headers,
+                    &self.records,
+                    options.backup,
+                    &mut self.timings,
+                ) {
                     Ok(_) => { /* Success - could add a status message */ }
                     Err(e) => self.error = Some(e),
                 }
@@ -148,48 +552,68 @@ impl SortBenchApp {
     }
 
     fn run_benchmarks(&mut self) {
-        self.results.clear();
-
-        let row_count = self.records.len();
-        let skip_slow = row_count > BUBBLE_SORT_ROW_LIMIT;
-
-        let mut algorithms: Vec<(&str, fn(&mut [Record], usize) -> f64)> = vec![
-            (
-                "Std Sort",
-                algorithms::standardsort::sort as fn(&mut [Record], usize) -> f64,
-            ),
-            ("Merge Sort", algorithms::mergesort::sort),
-            ("Quick Sort", algorithms::quicksort::sort),
-        ];
-
-        if !skip_slow {
-            algorithms.extend_from_slice(&[
-                ("Bubble Sort", algorithms::bubblesort::sort),
-                ("Insertion Sort", algorithms::insertionsort::sort),
-            ]);
-        } else {
-            // Add a note explaining why some algorithms were skipped
-            self.results.push(BenchResult {
-                name: "Bubble Sort".to_string(),
-                duration_ms: 0.0,
-            });
-            self.results.push(BenchResult {
-                name: "Insertion Sort".to_string(),
-                duration_ms: 0.0,
-            });
-            self.results.push(BenchResult {
-                name: "(skipped: >1000 rows)".to_string(),
-                duration_ms: 0.0,
-            });
+        if self.compare_arena_storage && self.arena.is_none() {
+            self.load_arena();
         }
 
-        for (name, sort_fn) in algorithms {
-            let mut data = self.records.clone();
-            let time = sort_fn(&mut data, self.selected_column_index);
-            self.results.push(BenchResult {
-                name: name.to_string(),
-                duration_ms: time,
-            });
+        let environment = Environment::capture();
+        let options = BenchOptions {
+            compare_index_sort: self.compare_index_sort,
+            arena: self.arena.as_ref().filter(|_| self.compare_arena_storage),
+            track_allocations: self.track_allocations,
+            trials: 1,
+        };
+        self.results = bench::run_benchmarks(&self.records, self.selected_column_index, options);
+
+        // Time the remaining pipeline stages on a copy; the write stage only runs on export.
+        let mut data = self.records.clone();
+        pipeline::sort_with_keys(&mut data, self.selected_column_index, &mut self.timings);
+        let writer = self.export_format.writer(&self.export_options);
+        if let Err(e) =
+            io::serialize_table_timed(writer.as_ref(), &self.headers, &data, &mut self.timings)
+        {
+            self.error = Some(e);
+        }
+
+        let report = BenchReport {
+            dataset: DatasetInfo {
+                path: self
+                    .loaded_file_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default(),
+                format: self.input_format.name().to_string(),
+                rows: self.records.len(),
+                columns: self.headers.len(),
+            },
+            column: self.headers[self.selected_column_index].clone(),
+            params: BenchParams::from(&options),
+            results: self.results.clone(),
+            pipeline: self.timings,
+            environment,
+        };
+        if let Err(e) = self.history.record(report.clone()) {
+            self.error = Some(e);
+        }
+        self.report = Some(report);
+    }
+
+    fn export_results(&mut self) {
+        let Some(report) = &self.report else {
+            return;
+        };
+        let mut dialog = rfd::FileDialog::new().set_file_name(format!(
+            "benchmark_sorted_by_{}.{}",
+            report.column,
+            ReportFormat::default().extension()
+        ));
+        for format in ReportFormat::ALL {
+            dialog = dialog.add_filter(format.name(), &[format.extension()]);
+        }
+        if let Some(path) = dialog.save_file()
+            && let Err(e) = report.save(&path, ReportFormat::from_path(&path))
+        {
+            self.error = Some(e);
         }
     }
 }
@@ -0,0 +1,146 @@
+// Copyright (c) 2026 Neil Pandya
+
+// "History" panel: lists past runs from the history file and overlays two of
+// them on one chart with per-algorithm percentage deltas.
+
+use crate::history::{self, History};
+use crate::models::CsvError;
+use crate::report::BenchReport;
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Legend, Plot};
+
+#[derive(Default)]
+pub struct HistoryPanel {
+    /// `None` when the platform has no user data directory.
+    history: Option<History>,
+    /// Run the comparison is measured from ("A").
+    base: Option<u64>,
+    /// Run compared against the base ("B").
+    other: Option<u64>,
+}
+
+impl HistoryPanel {
+    /// Opens the history under the user data directory.
+    pub fn open() -> Result<Self, CsvError> {
+        let history = History::default_path()
+            .map(|path| History::open(&path))
+            .transpose()?;
+        Ok(Self {
+            history,
+            ..Self::default()
+        })
+    }
+
+    /// Appends a finished run to the history file.
+    pub fn record(&mut self, report: BenchReport) -> Result<(), CsvError> {
+        match &mut self.history {
+            Some(history) => history.record(report).map(drop),
+            None => Ok(()),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        let Some(history) = &self.history else {
+            ui.label("No user data directory; history is not saved.");
+            return;
+        };
+        if history.entries().is_empty() {
+            ui.label("No runs recorded yet.");
+            return;
+        }
+
+        ui.label("Pick run A and run B to compare them.");
+        egui::ScrollArea::vertical()
+            .max_height(150.0)
+            .show(ui, |ui| {
+                egui::Grid::new("history_runs")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("A");
+                        ui.strong("B");
+                        ui.strong("Run");
+                        ui.strong("Rows");
+                        ui.end_row();
+                        for entry in history.entries().iter().rev() {
+                            ui.radio_value(&mut self.base, Some(entry.id), "");
+                            ui.radio_value(&mut self.other, Some(entry.id), "");
+                            ui.label(entry.label());
+                            ui.label(entry.report.dataset.rows.to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        let (Some(base), Some(other)) = (
+            self.base.and_then(|id| history.get(id)),
+            self.other.and_then(|id| history.get(id)),
+        ) else {
+            return;
+        };
+        if let Some(reason) = history::profile_mismatch(&base.report, &other.report) {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!("⚠️ Not comparable: {}.", reason),
+            );
+            return;
+        }
+        let deltas = history::compare(&base.report, &other.report);
+
+        let bars = |offset: f64, time: fn(&history::Delta) -> Option<f64>| -> Vec<Bar> {
+            deltas
+                .iter()
+                .enumerate()
+                .filter_map(|(i, delta)| {
+                    time(delta).map(|ms| Bar::new(i as f64 + offset, ms).name(&delta.name))
+                })
+                .collect()
+        };
+        Plot::new("History Comparison")
+            .view_aspect(2.0)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(
+                    BarChart::new(bars(-0.2, |d| d.base_ms))
+                        .width(0.4)
+                        .name(format!("A: {}", base.label())),
+                );
+                plot_ui.bar_chart(
+                    BarChart::new(bars(0.2, |d| d.other_ms))
+                        .width(0.4)
+                        .name(format!("B: {}", other.label())),
+                );
+            });
+
+        egui::Grid::new("history_deltas")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Algorithm");
+                ui.strong("A (ms)");
+                ui.strong("B (ms)");
+                ui.strong("Change");
+                ui.end_row();
+                let ms =
+                    |value: Option<f64>| value.map_or("–".to_string(), |ms| format!("{:.3}", ms));
+                for delta in &deltas {
+                    ui.label(&delta.name);
+                    ui.label(ms(delta.base_ms));
+                    ui.label(ms(delta.other_ms));
+                    match delta.percent() {
+                        Some(percent) => {
+                            // Slower is red, faster is green.
+                            let color = if percent > 0.0 {
+                                egui::Color32::RED
+                            } else {
+                                egui::Color32::GREEN
+                            };
+                            ui.colored_label(color, format!("{:+.1}%", percent));
+                        }
+                        None => {
+                            ui.label("–");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) 2026 Neil Pandya
+
+// "Load options" window shown after picking a file: starts from the sniffed
+// dialect, lets the user override it and previews the first rows live.
+
+use crate::diagnostics::RowIssue;
+use crate::dialect::{Dialect, LoadOptions, RaggedRows, format_dialect_char, parse_dialect_char};
+use crate::encoding::parse_encoding;
+use crate::io;
+use crate::models::{CsvError, Record};
+use crate::readers::FixedWidthReader;
+use eframe::egui;
+use std::path::PathBuf;
+
+const PREVIEW_ROWS: usize = 10;
+
+pub enum LoadDialogAction {
+    Load(PathBuf, LoadOptions),
+    /// Read as fixed-width text; the header checkbox skips the title line.
+    LoadFixedWidth(PathBuf, FixedWidthReader),
+    Cancel,
+}
+
+pub struct LoadDialog {
+    path: PathBuf,
+    sniffed: Dialect,
+    delimiter: String,
+    quote: String,
+    escape: String,
+    comment: String,
+    encoding: String,
+    /// Fixed-width column spec (`name:width,...`); blank reads the file as CSV.
+    fixed_width: String,
+    has_headers: bool,
+    trim: bool,
+    ragged_rows: RaggedRows,
+    /// Options the current preview was built from, to avoid re-reading every frame.
+    previewed: Option<(LoadOptions, String)>,
+    preview: Result<(Vec<String>, Vec<Record>), CsvError>,
+    preview_issues: Vec<RowIssue>,
+}
+
+impl LoadDialog {
+    pub fn new(path: PathBuf) -> Self {
+        let sniffed = io::detect_dialect(&path);
+        let mut dialog = Self {
+            path,
+            sniffed,
+            delimiter: format_dialect_char(sniffed.delimiter),
+            quote: format_dialect_char(sniffed.quote),
+            escape: sniffed.escape.map(format_dialect_char).unwrap_or_default(),
+            comment: String::new(),
+            encoding: sniffed.encoding.name().to_string(),
+            fixed_width: String::new(),
+            has_headers: sniffed.has_headers,
+            trim: false,
+            ragged_rows: RaggedRows::default(),
+            previewed: None,
+            preview: Ok((Vec::new(), Vec::new())),
+            preview_issues: Vec::new(),
+        };
+        dialog.refresh_preview();
+        dialog
+    }
+
+    /// The options as currently entered, or a message naming the invalid field.
+    fn options(&self) -> Result<LoadOptions, String> {
+        let required = |name: &str, text: &str| {
+            parse_dialect_char(text).ok_or_else(|| format!("{} must be a single character", name))
+        };
+        let optional = |name: &str, text: &str| {
+            if text.is_empty() {
+                Ok(None)
+            } else {
+                required(name, text).map(Some)
+            }
+        };
+        Ok(LoadOptions {
+            dialect: Dialect {
+                delimiter: required("Delimiter", &self.delimiter)?,
+                quote: required("Quote", &self.quote)?,
+                escape: optional("Escape", &self.escape)?,
+                has_headers: self.has_headers,
+                encoding: parse_encoding(&self.encoding)
+                    .ok_or_else(|| format!("Unknown encoding: {}", self.encoding))?,
+                ..self.sniffed
+            },
+            comment: optional("Comment", &self.comment)?,
+            trim: self.trim,
+            ragged_rows: self.ragged_rows,
+        })
+    }
+
+    /// The fixed-width reader for the entered spec, if one was entered.
+    fn fixed_width_reader(&self) -> Result<Option<FixedWidthReader>, String> {
+        if self.fixed_width.trim().is_empty() {
+            return Ok(None);
+        }
+        FixedWidthReader::from_spec(&self.fixed_width, self.has_headers)
+            .map(Some)
+            .map_err(|e| format!("Fixed-width columns: {}", e))
+    }
+
+    fn refresh_preview(&mut self) {
+        let (Ok(options), Ok(fixed_width)) = (self.options(), self.fixed_width_reader()) else {
+            return;
+        };
+        let key = (options, self.fixed_width.clone());
+        if self.previewed.as_ref() != Some(&key) {
+            self.preview_issues.clear();
+            self.preview = match fixed_width {
+                Some(reader) => io::open_input(&self.path).and_then(|mut input| {
+                    reader.read_rows(&mut input, &mut self.preview_issues, PREVIEW_ROWS)
+                }),
+                None => {
+                    io::preview_csv(&self.path, &options, PREVIEW_ROWS, &mut self.preview_issues)
+                }
+            };
+            self.previewed = Some(key);
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<LoadDialogAction> {
+        let mut action = None;
+        egui::Window::new("Load Options")
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!("File: {}", self.path.display()));
+                ui.label(format!("Sniffed: {}", self.sniffed));
+                ui.separator();
+
+                egui::Grid::new("load_options").show(ui, |ui| {
+                    for (label, text) in [
+                        ("Delimiter (\\t = tab)", &mut self.delimiter),
+                        ("Quote", &mut self.quote),
+                        ("Escape (blank = doubled quotes)", &mut self.escape),
+                        ("Comment (blank = none)", &mut self.comment),
+                    ] {
+                        ui.label(label);
+                        ui.add(egui::TextEdit::singleline(text).desired_width(40.0));
+                        ui.end_row();
+                    }
+                    ui.label("Encoding (e.g. windows-1252)");
+                    ui.add(egui::TextEdit::singleline(&mut self.encoding).desired_width(120.0));
+                    ui.end_row();
+                    ui.label("Fixed-width columns (e.g. id:4,name:12)");
+                    ui.add(egui::TextEdit::singleline(&mut self.fixed_width).desired_width(200.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut self.has_headers, "First row is a header");
+                ui.checkbox(&mut self.trim, "Trim whitespace");
+                egui::ComboBox::from_label("Ragged rows")
+                    .selected_text(self.ragged_rows.description())
+                    .show_ui(ui, |ui| {
+                        for policy in RaggedRows::ALL {
+                            ui.selectable_value(
+                                &mut self.ragged_rows,
+                                policy,
+                                policy.description(),
+                            );
+                        }
+                    });
+
+                self.refresh_preview();
+                ui.separator();
+                let options = self.options();
+                let fixed_width = self.fixed_width_reader();
+                match (options.as_ref().and(fixed_width.as_ref()), &self.preview) {
+                    (Err(message), _) => {
+                        ui.colored_label(egui::Color32::RED, message);
+                    }
+                    (Ok(_), Err(e)) => {
+                        ui.colored_label(egui::Color32::RED, e.to_string());
+                    }
+                    (Ok(_), Ok((headers, records))) => {
+                        show_preview(ui, headers, records);
+                        if !self.preview_issues.is_empty() {
+                            ui.colored_label(
+                                egui::Color32::YELLOW,
+                                format!(
+                                    "{} ragged row(s) in the preview",
+                                    self.preview_issues.len()
+                                ),
+                            );
+                        }
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    if let (Ok(options), Ok(fixed_width)) = (options, fixed_width)
+                        && self.preview.is_ok()
+                        && ui.button("Load").clicked()
+                    {
+                        let path = self.path.clone();
+                        action = Some(match fixed_width {
+                            Some(reader) => LoadDialogAction::LoadFixedWidth(path, reader),
+                            None => LoadDialogAction::Load(path, options),
+                        });
+                    }
+                    if ui.button("Cancel").clicked() {
+                        action = Some(LoadDialogAction::Cancel);
+                    }
+                });
+            });
+        action
+    }
+}
+
+fn show_preview(ui: &mut egui::Ui, headers: &[String], records: &[Record]) {
+    egui::ScrollArea::horizontal().show(ui, |ui| {
+        egui::Grid::new("load_preview")
+            .striped(true)
+            .show(ui, |ui| {
+                for header in headers {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for record in records {
+                    for cell in record {
+                        ui.label(cell);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
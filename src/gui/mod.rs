@@ -3,13 +3,16 @@
 // GUI Module Gateway
 // This file will define how we run the GUI application.
 
-pub mod app;
+pub(crate) mod app;
+pub(crate) mod history_panel;
+pub(crate) mod load_dialog;
+pub(crate) mod suite_panel;
 
 pub fn run_app() -> eframe::Result<()> {
     let options = eframe::NativeOptions::default();
     eframe::run_native(
         "CSV Sort Benchmark",
         options,
-        Box::new(|_cc| Ok(Box::new(app::SortBenchApp::default()))),
+        Box::new(|_cc| Ok(Box::new(app::SortBenchApp::new()))),
     )
 }
@@ -0,0 +1,150 @@
+// Copyright (c) 2026 Neil Pandya
+
+// "Suite" panel: opens a TOML benchmark suite, runs it on a worker thread and
+// shows the combined results, which can be exported like a single run's report.
+
+use crate::models::CsvError;
+use crate::report::ReportFormat;
+use crate::suite::{Suite, SuiteReport};
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// What the worker thread reports back while a suite runs.
+enum Progress {
+    /// The group it has started, as passed to `Suite::run`'s callback.
+    Group(String),
+    Done(Result<SuiteReport, CsvError>),
+}
+
+/// A suite running on the worker thread.
+struct Running {
+    path: PathBuf,
+    progress: Receiver<Progress>,
+    group: String,
+}
+
+#[derive(Default)]
+pub struct SuitePanel {
+    /// The suite file last run.
+    path: Option<PathBuf>,
+    report: Option<SuiteReport>,
+    running: Option<Running>,
+}
+
+impl SuitePanel {
+    /// Asks for a suite file and starts running it on a worker thread, which
+    /// repaints `ctx` as it moves from group to group.
+    pub fn open_and_run(&mut self, ctx: &egui::Context) -> Result<(), CsvError> {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Benchmark suite", &["toml"])
+            .pick_file()
+        else {
+            return Ok(());
+        };
+        let suite = Suite::load(&path)?;
+        let (sender, progress) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let report = suite.run(&mut |group| {
+                let _ = sender.send(Progress::Group(group.to_string()));
+                ctx.request_repaint();
+            });
+            let _ = sender.send(Progress::Done(report));
+            ctx.request_repaint();
+        });
+        self.running = Some(Running {
+            path,
+            progress,
+            group: String::new(),
+        });
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Picks up the worker's progress; call once per frame.
+    pub fn poll(&mut self) -> Result<(), CsvError> {
+        let Some(running) = &mut self.running else {
+            return Ok(());
+        };
+        loop {
+            match running.progress.try_recv() {
+                Ok(Progress::Group(group)) => running.group = group,
+                Ok(Progress::Done(report)) => {
+                    let running = self.running.take().expect("checked above");
+                    self.path = Some(running.path);
+                    self.report = Some(report?);
+                    return Ok(());
+                }
+                Err(TryRecvError::Empty) => return Ok(()),
+                Err(TryRecvError::Disconnected) => {
+                    self.running = None;
+                    return Err(std::io::Error::other("the suite run stopped unexpectedly").into());
+                }
+            }
+        }
+    }
+
+    /// A spinner and the current group while a suite runs.
+    pub fn show_progress(&self, ui: &mut egui::Ui) {
+        if let Some(running) = &self.running {
+            ui.spinner();
+            ui.label(format!("Running {}", running.group));
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Result<(), CsvError> {
+        let (Some(path), Some(report)) = (&self.path, &self.report) else {
+            ui.label("Open a suite file to run it.");
+            return Ok(());
+        };
+
+        let mut export = false;
+        ui.horizontal(|ui| {
+            ui.label(format!("{} ({} groups)", path.display(), report.runs.len()));
+            export = ui.button("Export Suite Report").clicked();
+        });
+
+        egui::ScrollArea::vertical()
+            .max_height(250.0)
+            .show(ui, |ui| {
+                egui::Grid::new("suite_results")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Dataset");
+                        ui.strong("Distribution");
+                        ui.strong("Column");
+                        ui.strong("Algorithm");
+                        ui.strong("Time (ms)");
+                        ui.end_row();
+                        for (run, result) in report.rows() {
+                            ui.label(&run.dataset);
+                            ui.label(run.distribution.name());
+                            ui.label(&run.report.column);
+                            ui.label(&result.name);
+                            ui.label(format!("{:.3}", result.duration_ms));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if !export {
+            return Ok(());
+        }
+        let mut dialog = rfd::FileDialog::new().set_file_name(format!(
+            "suite_results.{}",
+            ReportFormat::default().extension()
+        ));
+        for format in ReportFormat::ALL {
+            dialog = dialog.add_filter(format.name(), &[format.extension()]);
+        }
+        match dialog.save_file() {
+            Some(path) => report.save(&path, ReportFormat::from_path(&path)),
+            None => Ok(()),
+        }
+    }
+}
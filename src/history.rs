@@ -0,0 +1,325 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Benchmark history: every run is appended to a JSON Lines file under the
+// user data directory, so past runs can be listed and compared.
+
+use crate::algorithms::BenchResult;
+use crate::models::CsvError;
+use crate::report::BenchReport;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One recorded run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Increasing number identifying the run within its history file.
+    pub id: u64,
+    /// Local time the run was recorded, RFC 3339.
+    pub timestamp: String,
+    pub report: BenchReport,
+}
+
+impl HistoryEntry {
+    /// `#id time file (column)`, for lists and legends, with `[debug]`
+    /// appended for runs of a debug build.
+    pub fn label(&self) -> String {
+        let file = Path::new(&self.report.dataset.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.report.dataset.path.clone());
+        format!(
+            "#{} {} {} ({}){}",
+            self.id,
+            self.timestamp
+                .get(..19)
+                .unwrap_or(&self.timestamp)
+                .replace('T', " "),
+            file,
+            self.report.column,
+            if self.report.environment.is_debug() {
+                " [debug]"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// An append-only history file and the entries read from it.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// `<data dir>/csv-sort-bench/history.jsonl`, e.g. under `~/.local/share` on Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("csv-sort-bench").join("history.jsonl"))
+    }
+
+    /// Reads the history at `path`; a missing file is an empty history.
+    /// Lines that no longer parse (e.g. from an older version) are skipped.
+    pub fn open(path: &Path) -> Result<Self, CsvError> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => text
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(CsvError::from(e).with_path(path)),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, id: u64) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Appends `report` as a new entry, creating the file and its directory
+    /// if needed. Its id follows the highest one on file, which need not be
+    /// the last line's.
+    pub fn record(&mut self, report: BenchReport) -> Result<&HistoryEntry, CsvError> {
+        let entry = HistoryEntry {
+            id: self.entries.iter().map(|entry| entry.id).max().unwrap_or(0) + 1,
+            timestamp: chrono::Local::now().to_rfc3339(),
+            report,
+        };
+        let mut line =
+            serde_json::to_vec(&entry).map_err(|e| CsvError::from(std::io::Error::from(e)))?;
+        line.push(b'\n');
+
+        let io_error = |e: std::io::Error| CsvError::from(e).with_path(&self.path);
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&line))
+            .map_err(io_error)?;
+
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("just pushed"))
+    }
+}
+
+/// One algorithm's time in two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub name: String,
+    pub base_ms: Option<f64>,
+    pub other_ms: Option<f64>,
+}
+
+impl Delta {
+    /// Change from the base run in percent; positive means slower.
+    pub fn percent(&self) -> Option<f64> {
+        match (self.base_ms, self.other_ms) {
+            (Some(base), Some(other)) if base > 0.0 => Some((other - base) / base * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Why two runs' timings can't be compared, if they came from builds with
+/// different profiles. Reports from before profiles were recorded pass.
+pub fn profile_mismatch(base: &BenchReport, other: &BenchReport) -> Option<String> {
+    let (base, other) = (&base.environment.profile, &other.environment.profile);
+    (!base.is_empty() && !other.is_empty() && base != other).then(|| {
+        format!(
+            "a {} build cannot be compared with a {} build; rerun both with the same profile",
+            base, other
+        )
+    })
+}
+
+/// Pairs up the results of two runs by algorithm name, in the base run's
+/// order followed by anything only the other run has. Skipped placeholders
+/// have no time and are left out.
+pub fn compare(base: &BenchReport, other: &BenchReport) -> Vec<Delta> {
+    fn measured(report: &BenchReport) -> Vec<&BenchResult> {
+        report
+            .results
+            .iter()
+            .filter(|result| !result.skipped)
+            .collect()
+    }
+    let (base, other) = (measured(base), measured(other));
+    let time = |results: &[&BenchResult], name: &str| {
+        results
+            .iter()
+            .find(|result| result.name == name)
+            .map(|result| result.duration_ms)
+    };
+    let mut deltas: Vec<Delta> = base
+        .iter()
+        .map(|result| Delta {
+            name: result.name.clone(),
+            base_ms: Some(result.duration_ms),
+            other_ms: time(&other, &result.name),
+        })
+        .collect();
+    for result in &other {
+        if time(&base, &result.name).is_none() {
+            deltas.push(Delta {
+                name: result.name.clone(),
+                base_ms: None,
+                other_ms: Some(result.duration_ms),
+            });
+        }
+    }
+    deltas
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::BenchResult;
+    use crate::environment::Environment;
+    use crate::pipeline::PipelineTimings;
+    use crate::report::{BenchParams, DatasetInfo};
+    use tempfile::TempDir;
+
+    fn report(times: &[(&str, f64)]) -> BenchReport {
+        BenchReport {
+            dataset: DatasetInfo {
+                path: "/data/students.csv".to_string(),
+                format: "csv".to_string(),
+                rows: 10,
+                columns: 2,
+            },
+            column: "age".to_string(),
+            params: BenchParams::default(),
+            results: times
+                .iter()
+                .map(|&(name, duration_ms)| BenchResult {
+                    name: name.to_string(),
+                    duration_ms,
+                    alloc_stats: None,
+                    samples_ms: Vec::new(),
+                    skipped: false,
+                })
+                .collect(),
+            pipeline: PipelineTimings::default(),
+            environment: Environment::default(),
+        }
+    }
+
+    #[test]
+    fn records_runs_and_reads_them_back() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("history.jsonl");
+
+        let mut history = History::open(&path).unwrap();
+        assert!(history.entries().is_empty());
+        history.record(report(&[("Std Sort", 1.0)])).unwrap();
+        history.record(report(&[("Std Sort", 2.0)])).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let history = History::open(&path).unwrap();
+        let ids: Vec<u64> = history.entries().iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(history.get(2).unwrap().report.results[0].duration_ms, 2.0);
+        assert!(
+            history
+                .get(1)
+                .unwrap()
+                .label()
+                .contains("students.csv (age)")
+        );
+    }
+
+    #[test]
+    fn numbers_new_runs_after_the_highest_id() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let lines: String = [7, 4]
+            .into_iter()
+            .map(|id| {
+                let entry = HistoryEntry {
+                    id,
+                    timestamp: "2026-01-02T03:04:05+00:00".to_string(),
+                    report: report(&[("Std Sort", 1.0)]),
+                };
+                serde_json::to_string(&entry).unwrap() + "\n"
+            })
+            .collect();
+        fs::write(&path, lines).unwrap();
+
+        let mut history = History::open(&path).unwrap();
+
+        assert_eq!(history.record(report(&[("Std Sort", 2.0)])).unwrap().id, 8);
+    }
+
+    #[test]
+    fn compares_runs_by_algorithm_name() {
+        let base = report(&[("Std Sort", 10.0), ("Merge Sort", 4.0)]);
+        let other = report(&[("Merge Sort", 5.0), ("Quick Sort", 1.0)]);
+
+        let deltas = compare(&base, &other);
+
+        let names: Vec<&str> = deltas.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["Std Sort", "Merge Sort", "Quick Sort"]);
+        assert_eq!(deltas[0].percent(), None);
+        assert_eq!(deltas[1].percent(), Some(25.0));
+        assert_eq!(deltas[2].base_ms, None);
+    }
+
+    #[test]
+    fn leaves_skipped_placeholders_out_of_comparisons() {
+        let base = report(&[("Std Sort", 10.0)]);
+        let mut other = report(&[("Std Sort", 9.0)]);
+        other
+            .results
+            .push(BenchResult::skipped("Bubble Sort".to_string()));
+
+        let names: Vec<String> = compare(&base, &other).into_iter().map(|d| d.name).collect();
+
+        assert_eq!(names, vec!["Std Sort"]);
+    }
+
+    #[test]
+    fn refuses_to_compare_debug_with_release_runs() {
+        let with_profile = |profile: &str| {
+            let mut report = report(&[("Std Sort", 1.0)]);
+            report.environment.profile = profile.to_string();
+            report
+        };
+        let entry = HistoryEntry {
+            id: 3,
+            timestamp: "2026-01-02T03:04:05+00:00".to_string(),
+            report: with_profile("debug"),
+        };
+
+        assert!(profile_mismatch(&with_profile("debug"), &with_profile("release")).is_some());
+        assert_eq!(
+            profile_mismatch(&with_profile("release"), &with_profile("release")),
+            None
+        );
+        assert_eq!(
+            profile_mismatch(&with_profile(""), &with_profile("debug")),
+            None
+        );
+        assert_eq!(
+            entry.label(),
+            "#3 2026-01-02 03:04:05 students.csv (age) [debug]"
+        );
+    }
+}
@@ -1,73 +1,599 @@
 /// Copyright (c) 2026 Neil Pandya
+use crate::arena::RecordArena;
+use crate::atomic;
+use crate::compression::Compression;
+use crate::diagnostics::RowChecker;
+pub use crate::diagnostics::{RowAction, RowIssue};
+use crate::dialect::{self, Dialect, ExportOptions, LoadOptions, SNIFF_SAMPLE_BYTES};
+use crate::encoding;
+use crate::memory;
 use crate::models::{CsvError, Record};
+use crate::pipeline::{PipelineTimings, Timed};
+use std::borrow::Cow;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Cursor, Read, Write};
 use std::path::Path;
+use std::time::{Duration, Instant};
 
+/// The available strategies for turning a file on disk into sortable rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadMethod {
+    /// `load_csv`: one owned `String` per cell.
+    Owned,
+    /// `load_csv_arena`: buffered reads into a single packed arena.
+    Arena,
+    /// `load_csv_mmap`: memory-mapped file, reused `ByteRecord`, packed arena.
+    Mapped,
+}
+
+impl LoadMethod {
+    pub const ALL: [LoadMethod; 3] = [LoadMethod::Owned, LoadMethod::Arena, LoadMethod::Mapped];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoadMethod::Owned => "Vec<Vec<String>>",
+            LoadMethod::Arena => "Arena (StringRecord reuse)",
+            LoadMethod::Mapped => "Arena (mmap + ByteRecord)",
+        }
+    }
+}
+
+/// Time and memory spent by a single load.
+#[derive(Debug, Clone)]
+pub struct LoadReport {
+    pub method: LoadMethod,
+    pub rows: usize,
+    pub duration_ms: f64,
+    /// Growth of the process's peak RSS during the load. Only Linux reports it
+    /// (see `memory::reset_peak_rss`); `None` elsewhere.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// A loaded table, with what the load observed on the way.
+#[derive(Debug, Clone, Default)]
+pub struct Loaded<R = Vec<Record>> {
+    pub headers: Vec<String>,
+    pub rows: R,
+    /// Every row a lenient `RaggedRows` policy adjusted or skipped.
+    pub issues: Vec<RowIssue>,
+    /// The read and parse stages; the later stages stay zero.
+    pub timings: PipelineTimings,
+}
+
+/// Loads `path` with a sniffed dialect and default options.
 pub fn load_csv(path: &std::path::Path) -> Result<(Vec<String>, Vec<Record>), CsvError> {
-    // Check file existence first
+    let loaded = load_csv_timed(path, None)?;
+    Ok((loaded.headers, loaded.rows))
+}
+
+/// `load_csv` with explicit options (`None` sniffs them from the file), keeping
+/// the row issues and stage timings.
+pub fn load_csv_timed(path: &Path, options: Option<&LoadOptions>) -> Result<Loaded, CsvError> {
+    load_streamed(path, |input, issues| parse_input(input, options, issues))
+}
+
+/// Streams `path` through `parse`. The read stage is the time spent reading
+/// and decompressing the file, the parse stage everything else.
+fn load_streamed<R>(
+    path: &Path,
+    parse: impl FnOnce(&mut dyn Read, &mut Vec<RowIssue>) -> Result<(Vec<String>, R), CsvError>,
+) -> Result<Loaded<R>, CsvError> {
+    let start = Instant::now();
+    let mut input = Timed::new(open_input(path)?);
+    let mut issues = Vec::new();
+    let (headers, rows) = parse(&mut input, &mut issues).map_err(|e| e.with_path(path))?;
+    let reading = input.elapsed();
+    let timings = PipelineTimings {
+        read_ms: reading.as_secs_f64() * 1000.0,
+        parse_ms: start.elapsed().saturating_sub(reading).as_secs_f64() * 1000.0,
+        ..PipelineTimings::default()
+    };
+    Ok(Loaded {
+        headers,
+        rows,
+        issues,
+        timings,
+    })
+}
+
+/// `load_csv` for any reader, e.g. stdin: decompresses it if the magic bytes
+/// say so, and sniffs the dialect when `options` is `None`.
+pub fn load_csv_from_reader(
+    reader: impl Read,
+    options: Option<&LoadOptions>,
+    issues: &mut Vec<RowIssue>,
+) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+    parse_input(open_reader(reader)?, options, issues)
+}
+
+/// Parses decompressed CSV input, sniffing the dialect from its start when
+/// `options` is `None`.
+fn parse_input(
+    input: impl Read,
+    options: Option<&LoadOptions>,
+    issues: &mut Vec<RowIssue>,
+) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+    match options {
+        Some(options) => parse_csv(input, options, issues),
+        None => {
+            let (dialect, input) = sniff_input(input)?;
+            parse_csv(input, &LoadOptions::from(dialect), issues)
+        }
+    }
+}
+
+/// Anything that can turn a file's (decompressed) contents into the same
+/// `(headers, rows)` table `load_csv` produces. CSV itself is read through
+/// `LoadOptions`; the other input formats live in `readers`.
+pub trait TableReader {
+    fn read_table(
+        &self,
+        input: &mut dyn Read,
+        issues: &mut Vec<RowIssue>,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError>;
+}
+
+impl TableReader for LoadOptions {
+    fn read_table(
+        &self,
+        input: &mut dyn Read,
+        issues: &mut Vec<RowIssue>,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        parse_csv(input, self, issues)
+    }
+}
+
+/// `load_csv_timed` for any input format: streams (and decompresses) `path`
+/// into `reader`.
+pub fn load_table_timed(path: &Path, reader: &dyn TableReader) -> Result<Loaded, CsvError> {
+    load_streamed(path, |input, issues| reader.read_table(input, issues))
+}
+
+/// Opens `path` for streaming, decompressing gzip, zstd and bzip2 on the fly.
+pub fn open_input(path: &Path) -> Result<Box<dyn Read>, CsvError> {
     if !path.exists() {
-        return Err(CsvError::FileNotFound(path.display().to_string()));
+        return Err(CsvError::FileNotFound {
+            path: path.to_path_buf(),
+        });
     }
-    let delimiter = detect_delimiter(path);
-    let mut rdr = csv::ReaderBuilder::new()
-        .delimiter(delimiter)
-        .from_path(path)
-        .map_err(|e| CsvError::ParseError(e.to_string()))?;
+    let io_error = |e: std::io::Error| CsvError::from(e).with_path(path);
+    let mut file = BufReader::new(File::open(path).map_err(io_error)?);
+    let compression = Compression::detect(path, file.fill_buf().map_err(io_error)?);
+    compression.decompress_reader(file).map_err(io_error)
+}
+
+/// `open_input` for any reader, e.g. stdin. Without a file name, compression
+/// is recognised by its magic bytes only.
+pub fn open_reader<'a>(reader: impl Read + 'a) -> Result<Box<dyn Read + 'a>, CsvError> {
+    let (magic, reader) = peek(reader, 4)?;
+    Ok(Compression::from_magic(&magic).decompress_reader(reader)?)
+}
+
+/// Reads up to `len` bytes from the start of `input`, returning them with a
+/// reader that still yields the whole input.
+pub(crate) fn peek<R: Read>(mut input: R, len: usize) -> std::io::Result<(Vec<u8>, impl Read)> {
+    let mut head = Vec::with_capacity(len);
+    (&mut input).take(len as u64).read_to_end(&mut head)?;
+    Ok((head.clone(), Cursor::new(head).chain(input)))
+}
+
+/// Parses CSV from a stream of (decompressed) bytes. Without a header row,
+/// columns are named `column_1..column_n`.
+pub fn parse_csv(
+    input: impl Read,
+    options: &LoadOptions,
+    issues: &mut Vec<RowIssue>,
+) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+    let mut rdr = options
+        .reader_builder()
+        .from_reader(encoding::decode_reader(input, options.dialect.encoding));
 
     // Extract headers
-    let headers = rdr
-        .headers()
-        .map_err(|e| CsvError::ParseError(e.to_string()))?
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
+    let headers = read_headers(&mut rdr, options)?;
 
     // Extract records
-    let records = rdr
-        .records()
-        .map(|result| result.map(|record| record.iter().map(|s| s.to_string()).collect()))
-        .collect::<Result<Vec<Record>, _>>()
-        .map_err(|e| CsvError::ParseError(e.to_string()))?;
+    let records = read_records(&mut rdr, headers.len(), options, issues, usize::MAX)?;
 
     Ok((headers, records))
 }
 
-pub fn detect_delimiter(path: &Path) -> u8 {
-    let candidates = [b',', b';', b'\t', b'|'];
-    let mut best_delimiter = b',';
-
-    if let Ok(file) = File::open(path) {
-        let reader = BufReader::new(file);
-        if let Some(Ok(first_line)) = reader.lines().next() {
-            let mut max_count = 0;
-            for &c in &candidates {
-                let count = first_line.chars().filter(|&ch| ch == c as char).count();
-                if count > max_count {
-                    max_count = count;
-                    best_delimiter = c;
+/// Reads up to `max_rows` records, applying `options.ragged_rows` to each.
+fn read_records<R: std::io::Read>(
+    rdr: &mut csv::Reader<R>,
+    expected_width: usize,
+    options: &LoadOptions,
+    issues: &mut Vec<RowIssue>,
+    max_rows: usize,
+) -> Result<Vec<Record>, CsvError> {
+    let mut checker = RowChecker::new(options.ragged_rows, expected_width, issues);
+    let mut records = Vec::new();
+    for result in rdr.records() {
+        if records.len() >= max_rows {
+            break;
+        }
+        match result {
+            Ok(record) => {
+                let line = record.position().map_or(0, |p| p.line());
+                if let Some(width) = checker.check(line, record.len()) {
+                    let mut row: Record =
+                        record.iter().take(width).map(|s| s.to_string()).collect();
+                    row.resize(width, String::new());
+                    records.push(row);
                 }
             }
+            Err(e) => checker.reject(e)?,
         }
     }
-    best_delimiter
+    Ok(records)
+}
+
+/// Reads just the first `max_rows` records, for previewing `options` before a full load.
+pub fn preview_csv(
+    path: &Path,
+    options: &LoadOptions,
+    max_rows: usize,
+    issues: &mut Vec<RowIssue>,
+) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+    let file = open_input(path)?;
+    let mut rdr = options
+        .reader_builder()
+        .from_reader(encoding::decode_reader(file, options.dialect.encoding));
+
+    let headers = read_headers(&mut rdr, options).map_err(|e| e.with_path(path))?;
+    let records = read_records(&mut rdr, headers.len(), options, issues, max_rows)
+        .map_err(|e| e.with_path(path))?;
+
+    Ok((headers, records))
+}
+
+/// Returns the header row, or generated `column_N` names when `options` says
+/// the file has none. In that case the first row is left in place as data.
+fn read_headers<R: std::io::Read>(
+    rdr: &mut csv::Reader<R>,
+    options: &LoadOptions,
+) -> Result<Vec<String>, CsvError> {
+    let first = rdr.headers()?;
+    if options.dialect.has_headers {
+        Ok(first.iter().map(|s| s.to_string()).collect())
+    } else {
+        Ok(generated_headers(first.len()))
+    }
+}
+
+/// Column names for headerless input: `column_1..column_n`.
+pub fn generated_headers(count: usize) -> Vec<String> {
+    (1..=count).map(|i| format!("column_{}", i)).collect()
 }
 
+/// Resolves a column given either by exact header name or by zero-based index.
+pub fn resolve_column(headers: &[String], spec: &str) -> Option<usize> {
+    headers
+        .iter()
+        .position(|h| h == spec)
+        .or_else(|| spec.parse().ok().filter(|&i| i < headers.len()))
+}
+
+/// Loads a CSV file into arena-backed storage instead of one `String` per cell.
+/// A single `StringRecord` is reused for every row.
+pub fn load_csv_arena(path: &Path, options: &LoadOptions) -> Result<Loaded<RecordArena>, CsvError> {
+    load_streamed(path, |input, issues| {
+        let mut rdr = options
+            .reader_builder()
+            .from_reader(encoding::decode_reader(input, options.dialect.encoding));
+
+        let headers = read_headers(&mut rdr, options)?;
+
+        let mut checker = RowChecker::new(options.ragged_rows, headers.len(), issues);
+        let mut arena = RecordArena::new();
+        let mut record = csv::StringRecord::new();
+        loop {
+            match rdr.read_record(&mut record) {
+                Ok(false) => break,
+                Ok(true) => {
+                    let line = record.position().map_or(0, |p| p.line());
+                    if let Some(width) = checker.check(line, record.len()) {
+                        arena.push_row(record.iter().chain(std::iter::repeat("")).take(width));
+                    }
+                }
+                Err(e) => checker.reject(e)?,
+            }
+        }
+        arena.shrink_to_fit();
+
+        Ok((headers, arena))
+    })
+}
+
+/// Memory-mapped load path: parses the mapping with a single reused
+/// `ByteRecord`, so the file is never read into a buffer and each cell is
+/// copied once, from the mapping into the arena. Its reads are page faults
+/// during parsing, so the whole load counts as the parse stage.
+pub fn load_csv_mmap(path: &Path, options: &LoadOptions) -> Result<Loaded<RecordArena>, CsvError> {
+    let start = Instant::now();
+    let file = File::open(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => CsvError::FileNotFound {
+            path: path.to_path_buf(),
+        },
+        _ => CsvError::from(e).with_path(path),
+    })?;
+    // SAFETY: the mapping is read-only and dropped before this function returns.
+    // Concurrent truncation by another process would fault, as with any mmap reader.
+    let mmap =
+        unsafe { memmap2::Mmap::map(&file) }.map_err(|e| CsvError::from(e).with_path(path))?;
+
+    // Borrows the mapping for uncompressed UTF-8; anything else needs one
+    // decompressed or transcoded copy.
+    let raw = match Compression::detect(path, &mmap) {
+        Compression::None => Cow::Borrowed(&mmap[..]),
+        compression => Cow::Owned(
+            compression
+                .decompress(&mmap)
+                .map_err(|e| CsvError::from(e).with_path(path))?,
+        ),
+    };
+    let text = encoding::decode(&raw, options.dialect.encoding);
+    let mut rdr = options.reader_builder().from_reader(&text[..]);
+
+    let headers = read_headers(&mut rdr, options).map_err(|e| e.with_path(path))?;
+
+    let mut issues = Vec::new();
+    let mut checker = RowChecker::new(options.ragged_rows, headers.len(), &mut issues);
+    let mut arena = RecordArena::new();
+    let mut record = csv::ByteRecord::new();
+    loop {
+        match rdr.read_byte_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                let line = record.position().map_or(0, |p| p.line());
+                let cells = match record
+                    .iter()
+                    .enumerate()
+                    .map(|(field, cell)| std::str::from_utf8(cell).map_err(|e| (field, e)))
+                    .collect::<Result<Vec<&str>, _>>()
+                {
+                    Ok(cells) => cells,
+                    Err((field, e)) => {
+                        let error =
+                            CsvError::parse(line, Some(field), format!("invalid UTF-8: {}", e));
+                        checker.reject_row(error).map_err(|e| e.with_path(path))?;
+                        continue;
+                    }
+                };
+                if let Some(width) = checker.check(line, cells.len()) {
+                    arena.push_row(cells.into_iter().chain(std::iter::repeat("")).take(width));
+                }
+            }
+            Err(e) => checker.reject(e).map_err(|e| e.with_path(path))?,
+        }
+    }
+    arena.shrink_to_fit();
+
+    Ok(Loaded {
+        headers,
+        rows: arena,
+        issues,
+        timings: PipelineTimings {
+            parse_ms: start.elapsed().as_secs_f64() * 1000.0,
+            ..PipelineTimings::default()
+        },
+    })
+}
+
+/// Loads `path` with the given method and reports its wall time and peak memory growth.
+pub fn measure_load(
+    path: &Path,
+    method: LoadMethod,
+    options: &LoadOptions,
+) -> Result<LoadReport, CsvError> {
+    let baseline = memory::reset_peak_rss()
+        .then(memory::current_rss_bytes)
+        .flatten();
+    let start = Instant::now();
+    let rows = match method {
+        LoadMethod::Owned => load_csv_timed(path, Some(options))?.rows.len(),
+        LoadMethod::Arena => load_csv_arena(path, options)?.rows.len(),
+        LoadMethod::Mapped => load_csv_mmap(path, options)?.rows.len(),
+    };
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let peak_memory_bytes = baseline
+        .zip(memory::peak_rss_bytes())
+        .map(|(before, peak)| peak.saturating_sub(before));
+
+    Ok(LoadReport {
+        method,
+        rows,
+        duration_ms,
+        peak_memory_bytes,
+    })
+}
+
+/// Sniffs the dialect from the first `SNIFF_SAMPLE_BYTES` of a stream, e.g.
+/// stdin, returning it with a reader that still starts at the beginning.
+pub fn sniff_input(input: impl Read) -> Result<(Dialect, impl Read), CsvError> {
+    let (sample, input) = peek(input, SNIFF_SAMPLE_BYTES)?;
+    Ok((dialect::sniff(&sample), input))
+}
+
+/// The delimiter `detect_dialect` finds, for callers that need nothing else.
+pub fn detect_delimiter(path: &Path) -> u8 {
+    detect_dialect(path).delimiter
+}
+
+/// Sniffs the dialect from the first `SNIFF_SAMPLE_BYTES` of the file,
+/// falling back to the default dialect if it cannot be read.
+pub fn detect_dialect(path: &Path) -> Dialect {
+    open_input(path)
+        .and_then(sniff_input)
+        .map_or_else(|_| Dialect::default(), |(dialect, _)| dialect)
+}
+
+/// The export-side counterpart of `TableReader`: streams the table into one
+/// file. CSV is written through `ExportOptions`; the other output formats live
+/// in `writers`.
+pub trait TableWriter {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError>;
+
+    /// Checks the written temp file before it replaces the destination; `rows`
+    /// is how many data rows were written.
+    fn verify(&self, _written: &Path, _rows: usize) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl TableWriter for ExportOptions {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError> {
+        serialize_csv(out, headers, records, self)
+    }
+
+    fn verify(&self, written: &Path, rows: usize) -> std::io::Result<()> {
+        let expected_rows = rows + usize::from(self.write_headers);
+        let rows = count_exported_rows(written, self)?;
+        if rows == expected_rows {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("wrote {} rows but read back {}", expected_rows, rows),
+            ))
+        }
+    }
+}
+
+/// Writes the table to `path` as CSV with the default export options.
 pub fn save_csv(path: &Path, headers: &[String], records: &[Record]) -> Result<(), CsvError> {
-    let mut wtr = csv::Writer::from_path(path).map_err(|e| CsvError::IoError(e.to_string()))?;
+    let options = ExportOptions::default();
+    save_csv_timed(
+        path,
+        headers,
+        records,
+        &options,
+        &mut PipelineTimings::default(),
+    )
+}
+
+/// `save_csv` in `options`' dialect, recording the serialize and write stages
+/// in `timings`; `save_table` takes the writer for any other format.
+pub fn save_csv_timed(
+    path: &Path,
+    headers: &[String],
+    records: &[Record],
+    options: &ExportOptions,
+    timings: &mut PipelineTimings,
+) -> Result<(), CsvError> {
+    save_table(path, options, headers, records, options.backup, timings)
+}
+
+/// Streams the table through `writer` into `out`, buffered. Not atomic and not
+/// verified, unlike `save_table`; e.g. for stdout.
+pub fn write_table_to(
+    out: impl Write,
+    writer: &dyn TableWriter,
+    headers: &[String],
+    records: &[Record],
+) -> Result<(), CsvError> {
+    let mut out = BufWriter::new(out);
+    writer.write_table(&mut out, headers, records)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Writes headers and rows to `path` with `writer`, recording the serialize and
+/// write stages separately in `timings`. The write is atomic (see
+/// `atomic::write_atomic`) and the written file must pass `writer.verify` (for
+/// CSV, a row count of the parsed-back file), which the write stage includes.
+pub fn save_table(
+    path: &Path,
+    writer: &dyn TableWriter,
+    headers: &[String],
+    records: &[Record],
+    backup: bool,
+    timings: &mut PipelineTimings,
+) -> Result<(), CsvError> {
+    let start = Instant::now();
+    let mut serializing = Duration::ZERO;
+    atomic::write_atomic_with(
+        path,
+        backup,
+        |out| {
+            let start = Instant::now();
+            let mut out = Timed::new(out);
+            writer.write_table(&mut out, headers, records)?;
+            serializing = start.elapsed().saturating_sub(out.elapsed());
+            Ok::<(), CsvError>(())
+        },
+        |written| writer.verify(written, records.len()),
+    )
+    .map_err(|e| e.with_path(path))?;
+    timings.serialize_ms = serializing.as_secs_f64() * 1000.0;
+    timings.write_ms = start.elapsed().saturating_sub(serializing).as_secs_f64() * 1000.0;
+
+    Ok(())
+}
+
+fn count_exported_rows(path: &Path, options: &ExportOptions) -> std::io::Result<usize> {
+    let file = BufReader::new(File::open(path)?);
+    let input = options.compression.decompress_reader(file)?;
+    let mut rdr = options
+        .reader_builder()
+        .from_reader(encoding::decode_reader(input, options.encoding));
+    let mut record = csv::ByteRecord::new();
+    let mut rows = 0;
+    while rdr.read_byte_record(&mut record)? {
+        rows += 1;
+    }
+    Ok(rows)
+}
+
+/// Runs `writer` over the table without keeping the output, recording the
+/// serialize stage in `timings`.
+pub fn serialize_table_timed(
+    writer: &dyn TableWriter,
+    headers: &[String],
+    records: &[Record],
+    timings: &mut PipelineTimings,
+) -> Result<(), CsvError> {
+    let start = Instant::now();
+    writer.write_table(&mut std::io::sink(), headers, records)?;
+    timings.serialize_ms = start.elapsed().as_secs_f64() * 1000.0;
+    Ok(())
+}
+
+/// Streams headers and rows to `out` as CSV in `options.encoding`, compressed
+/// with `options.compression`.
+pub fn serialize_csv(
+    out: impl Write,
+    headers: &[String],
+    records: &[Record],
+    options: &ExportOptions,
+) -> Result<(), CsvError> {
+    let compressed = options.compression.compress_writer(out)?;
+    let encoded = encoding::EncodeWriter::new(compressed, options.encoding, options.bom);
+    let mut wtr = options.writer_builder().from_writer(encoded);
 
     // Write headers
-    wtr.write_record(headers)
-        .map_err(|e| CsvError::IoError(e.to_string()))?;
+    if options.write_headers {
+        wtr.write_record(headers)?;
+    }
 
     // Write data rows
     for record in records {
-        wtr.write_record(record)
-            .map_err(|e| CsvError::IoError(e.to_string()))?;
+        wtr.write_record(record)?;
     }
 
-    wtr.flush().map_err(|e| CsvError::IoError(e.to_string()))?;
-
+    let encoded = wtr
+        .into_inner()
+        .map_err(|e| CsvError::from(e.into_error()))?;
+    encoded.finish()?.finish()?;
     Ok(())
 }
 
@@ -75,10 +601,22 @@ pub fn save_csv(path: &Path, headers: &[String], records: &[Record]) -> Result<(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dialect::{LineTerminator, Quoting, RaggedRows};
     use std::fs;
     use std::io::Write;
     use tempfile::TempDir;
 
+    #[test]
+    fn resolves_columns_by_name_then_index() {
+        let headers = vec!["name".to_string(), "age".to_string(), "0".to_string()];
+
+        assert_eq!(resolve_column(&headers, "age"), Some(1));
+        assert_eq!(resolve_column(&headers, "1"), Some(1));
+        assert_eq!(resolve_column(&headers, "0"), Some(2)); // header name wins
+        assert_eq!(resolve_column(&headers, "7"), None);
+        assert_eq!(resolve_column(&headers, "salary"), None);
+    }
+
     #[test]
     fn load_csv_returns_error_for_nonexistent_file() {
         let nonexistent = std::path::PathBuf::from("/this/file/does/not/exist.csv");
@@ -86,9 +624,9 @@ mod tests {
         assert!(result.is_err());
         if let Err(e) = result {
             match e {
-                CsvError::FileNotFound(path) => {
+                CsvError::FileNotFound { path } => {
                     // The path should contain the invalid file path
-                    assert!(path.contains("/this/file/does/not/exist.csv"));
+                    assert_eq!(path, nonexistent);
                 }
                 _ => panic!("Expected FileNotFound error, got {:?}", e),
             }
@@ -108,22 +646,228 @@ mod tests {
         assert!(result.is_err());
         if let Err(e) = result {
             match e {
-                CsvError::ParseError(_) => {} // Expected
-                _ => panic!("Expected ParseError"),
+                CsvError::Parse {
+                    path, line, byte, ..
+                } => {
+                    assert_eq!(path.as_deref(), Some(file_path.as_path()));
+                    assert_eq!(line, Some(2));
+                    assert_eq!(byte, Some(16));
+                }
+                _ => panic!("Expected Parse error, got {:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn lenient_policies_repair_or_skip_ragged_rows() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("ragged.csv");
+        fs::write(
+            &file_path,
+            "name,age,salary\nAlice,30\nBob,25,45000\nCarol,41,1,2\n",
+        )
+        .unwrap();
+        let mut options = LoadOptions::from(detect_dialect(&file_path));
+
+        options.ragged_rows = RaggedRows::PadOrTruncate;
+        let Loaded {
+            rows: records,
+            issues,
+            ..
+        } = load_csv_timed(&file_path, Some(&options)).unwrap();
+        assert_eq!(records[0], vec!["Alice", "30", ""]);
+        assert_eq!(records[2], vec!["Carol", "41", "1"]);
+        let lines: Vec<u64> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, vec![2, 4]);
+        let arena = load_csv_arena(&file_path, &options).unwrap();
+        assert_eq!(
+            arena.rows.row(0).collect::<Vec<_>>(),
+            vec!["Alice", "30", ""]
+        );
+        assert_eq!(arena.issues, issues);
+
+        options.ragged_rows = RaggedRows::Skip;
+        let Loaded {
+            rows: records,
+            issues,
+            ..
+        } = load_csv_timed(&file_path, Some(&options)).unwrap();
+        assert_eq!(records, vec![vec!["Bob", "25", "45000"]]);
+        assert_eq!(issues.len(), 2);
+        let mapped = load_csv_mmap(&file_path, &options).unwrap();
+        assert_eq!(mapped.rows.len(), 1);
+        assert_eq!(mapped.issues, issues);
+    }
+
+    #[test]
+    fn mmap_load_reports_the_field_with_invalid_utf8() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("latin1.csv");
+        fs::write(&file_path, b"name,city\nAda,London\nJos\xe9,M\xe1laga\n").unwrap();
+        // Forced to UTF-8; sniffing would pick Windows-1252 and load it cleanly.
+        let options = LoadOptions::default();
+
+        match load_csv_mmap(&file_path, &options) {
+            Err(CsvError::Parse {
+                line, field, path, ..
+            }) => {
+                assert_eq!(line, Some(3));
+                assert_eq!(field, Some(0));
+                assert!(path.is_some());
+            }
+            other => panic!("Expected Parse error, got {:?}", other.map(|_| ())),
+        }
+        let error = load_csv_timed(&file_path, Some(&options)).unwrap_err();
+        assert!(matches!(
+            error,
+            CsvError::Parse {
+                line: Some(3),
+                field: Some(0),
+                ..
             }
+        ));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Parse error in {} at line 3, byte 21, field 1: invalid UTF-8 after byte 3 of the field",
+                file_path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn transcodes_legacy_and_utf16_files_on_load_and_export() {
+        let dir = TempDir::new().unwrap();
+        let latin1 = dir.path().join("latin1.csv");
+        fs::write(
+            &latin1,
+            b"name,city\nJos\xe9,M\xe1laga\nFran\xe7ois,Orl\xe9ans\n",
+        )
+        .unwrap();
+        let utf16 = dir.path().join("utf16.csv");
+        let text = "name,city\nJosé,Málaga\nFrançois,Orléans\n";
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        fs::write(&utf16, &bytes).unwrap();
+
+        for (path, encoding) in [
+            (&latin1, encoding_rs::WINDOWS_1252),
+            (&utf16, encoding_rs::UTF_16LE),
+        ] {
+            let options = LoadOptions::from(detect_dialect(path));
+            assert_eq!(options.dialect.encoding, encoding);
+            let mut timings = PipelineTimings::default();
+            let Loaded {
+                headers,
+                rows: records,
+                ..
+            } = load_csv_timed(path, Some(&options)).unwrap();
+            assert_eq!(headers, vec!["name", "city"]);
+            assert_eq!(records[0], vec!["José", "Málaga"]);
+            let arena = load_csv_arena(path, &options).unwrap().rows;
+            assert_eq!(arena.cell(1, 0), Some("François"));
+            let arena = load_csv_mmap(path, &options).unwrap().rows;
+            assert_eq!(arena.cell(1, 1), Some("Orléans"));
+
+            let output = dir.path().join("out.csv");
+            let export = ExportOptions::from(&options).keep_encoding(&options.dialect);
+            save_csv_timed(&output, &headers, &records, &export, &mut timings).unwrap();
+            assert_eq!(fs::read(&output).unwrap(), fs::read(path).unwrap());
         }
     }
 
+    #[test]
+    fn loads_and_exports_compressed_files() {
+        let dir = TempDir::new().unwrap();
+        let text = b"name;age\nAlice;30\nBob;25\n".to_vec();
+        let compressions = [Compression::Gzip, Compression::Zstd, Compression::Bzip2];
+        for compression in compressions.into_iter().filter(Compression::is_supported) {
+            // No extension: detected from the magic bytes alone.
+            let path = dir.path().join(format!("data_{}", compression.name()));
+            fs::write(&path, compression.compress(text.clone()).unwrap()).unwrap();
+
+            let options = LoadOptions::from(detect_dialect(&path));
+            assert_eq!(options.dialect.delimiter, b';');
+            let mut timings = PipelineTimings::default();
+            let Loaded {
+                headers,
+                rows: records,
+                ..
+            } = load_csv_timed(&path, Some(&options)).unwrap();
+            assert_eq!(records[1], vec!["Bob", "25"]);
+            assert_eq!(load_csv_arena(&path, &options).unwrap().rows.len(), 2);
+            assert_eq!(load_csv_mmap(&path, &options).unwrap().rows.len(), 2);
+
+            let export = ExportOptions {
+                compression,
+                ..ExportOptions::default()
+            };
+            let output = dir.path().join("out");
+            save_csv_timed(&output, &headers, &records, &export, &mut timings).unwrap();
+            let mut written = Vec::new();
+            open_input(&output)
+                .unwrap()
+                .read_to_end(&mut written)
+                .unwrap();
+            assert_eq!(written, b"name,age\nAlice,30\nBob,25\n");
+        }
+    }
+
+    #[test]
+    fn loads_from_and_saves_to_any_reader_and_writer() {
+        let text = b"name;age\nAlice;30\nBob;25\n".to_vec();
+        let gzipped = Compression::Gzip.compress(text.clone()).unwrap();
+
+        let (headers, records) = load_csv_from_reader(&gzipped[..], None, &mut Vec::new()).unwrap();
+        assert_eq!(headers, vec!["name", "age"]);
+        assert_eq!(records[1], vec!["Bob", "25"]);
+
+        let options = LoadOptions::from(dialect::sniff(&text));
+        let mut out = Vec::new();
+        write_table_to(&mut out, &ExportOptions::from(&options), &headers, &records).unwrap();
+        assert_eq!(out, text);
+    }
+
+    #[test]
+    fn export_preserves_the_source_dialect_unless_overridden() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("input.csv");
+        let source = b"\xEF\xBB\xBF\"name\";\"age\"\r\n\"Alice\";\"30\"\r\n\"Bob\";\"25\"\r\n";
+        fs::write(&input, source).unwrap();
+        let options = LoadOptions::from(detect_dialect(&input));
+        let mut timings = PipelineTimings::default();
+        let Loaded {
+            headers,
+            rows: records,
+            ..
+        } = load_csv_timed(&input, Some(&options)).unwrap();
+
+        let output = dir.path().join("output.csv");
+        let mut export = ExportOptions::from(&options);
+        save_csv_timed(&output, &headers, &records, &export, &mut timings).unwrap();
+        assert_eq!(fs::read(&output).unwrap(), source);
+
+        export.delimiter = b'\t';
+        export.quoting = Quoting::Necessary;
+        export.terminator = LineTerminator::Lf;
+        export.bom = false;
+        save_csv_timed(&output, &headers, &records, &export, &mut timings).unwrap();
+        assert_eq!(
+            fs::read(&output).unwrap(),
+            b"name\tage\nAlice\t30\nBob\t25\n"
+        );
+    }
+
     #[test]
     fn save_csv_returns_io_error_for_invalid_path() {
         // This should fail due to invalid permissions or non-writable path
         let invalid_path = std::path::PathBuf::from("/nonexistent/directory/file.csv");
-        let result = save_csv(&invalid_path, &vec!["col1".into()], &vec![]);
+        let result = save_csv(&invalid_path, &["col1".into()], &[]);
         assert!(result.is_err());
         if let Err(e) = result {
             match e {
-                CsvError::IoError(_) => {} // Expected
-                _ => panic!("Expected IoError"),
+                CsvError::Io { path, .. } => assert_eq!(path, Some(invalid_path)),
+                _ => panic!("Expected Io error, got {:?}", e),
             }
         }
     }
@@ -204,6 +948,70 @@ mod tests {
         assert!(records.is_empty()); // No data rows
     }
 
+    #[test]
+    fn load_csv_arena_matches_load_csv() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("arena.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "name;age").unwrap();
+        writeln!(file, "Alice;30").unwrap();
+        writeln!(file, "\"Bob; Jr\";25").unwrap();
+
+        let (headers, records) = load_csv(&file_path).unwrap();
+        let options = LoadOptions::from(detect_dialect(&file_path));
+        let Loaded {
+            headers: arena_headers,
+            rows: arena,
+            ..
+        } = load_csv_arena(&file_path, &options).unwrap();
+
+        assert_eq!(arena_headers, headers);
+        assert_eq!(arena.len(), records.len());
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(arena.row(i).collect::<Vec<_>>(), *record);
+        }
+    }
+
+    #[test]
+    fn load_csv_mmap_matches_load_csv() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("mapped.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "name,city").unwrap();
+        writeln!(file, "Alice,\"Zürich, CH\"").unwrap();
+        writeln!(file, "Bob,Oslo").unwrap();
+
+        let (headers, records) = load_csv(&file_path).unwrap();
+        let options = LoadOptions::from(detect_dialect(&file_path));
+        let Loaded {
+            headers: mapped_headers,
+            rows: arena,
+            ..
+        } = load_csv_mmap(&file_path, &options).unwrap();
+
+        assert_eq!(mapped_headers, headers);
+        assert_eq!(arena.len(), records.len());
+        assert_eq!(arena.cell(0, 1), Some("Zürich, CH"));
+    }
+
+    #[test]
+    fn measure_load_reports_row_count_for_every_method() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("measured.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "id,value").unwrap();
+        for i in 0..50 {
+            writeln!(file, "{},{}", i, i * 2).unwrap();
+        }
+
+        let options = LoadOptions::from(detect_dialect(&file_path));
+        for method in LoadMethod::ALL {
+            let report = measure_load(&file_path, method, &options).unwrap();
+            assert_eq!(report.rows, 50);
+            assert!(report.duration_ms >= 0.0);
+        }
+    }
+
     #[test]
     fn detect_delimiter_identifies_comma() {
         let dir = TempDir::new().unwrap();
@@ -228,6 +1036,121 @@ mod tests {
         assert_eq!(detected, b';');
     }
 
+    #[test]
+    fn load_csv_timed_records_read_and_parse_stages() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("timed.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "name|age").unwrap();
+        writeln!(file, "Alice|30").unwrap();
+
+        let Loaded {
+            headers,
+            rows: records,
+            timings,
+            ..
+        } = load_csv_timed(&file_path, None).unwrap();
+
+        assert_eq!(headers, vec!["name", "age"]);
+        assert_eq!(records, vec![vec!["Alice", "30"]]);
+        assert!(timings.read_ms > 0.0);
+        assert!(timings.parse_ms > 0.0);
+        assert_eq!(timings.sort_ms, 0.0);
+    }
+
+    #[test]
+    fn load_csv_respects_quoted_delimiters_in_header() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("quoted_header.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "\"Last, First\";Age").unwrap();
+        writeln!(file, "\"Doe, John\";25").unwrap();
+
+        let (headers, records) = load_csv(&file_path).unwrap();
+
+        assert_eq!(headers, vec!["Last, First", "Age"]);
+        assert_eq!(records[0], vec!["Doe, John", "25"]);
+    }
+
+    #[test]
+    fn load_options_override_the_sniffed_dialect() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("override.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "# exported 2026-01-01").unwrap();
+        writeln!(file, "a ; 1").unwrap();
+        writeln!(file, "b ; 2 ; extra").unwrap();
+
+        let options = LoadOptions {
+            dialect: Dialect {
+                delimiter: b';',
+                has_headers: false,
+                ..Dialect::default()
+            },
+            comment: Some(b'#'),
+            trim: true,
+            ragged_rows: RaggedRows::Keep,
+        };
+        let Loaded {
+            headers,
+            rows: records,
+            ..
+        } = load_csv_timed(&file_path, Some(&options)).unwrap();
+
+        assert_eq!(headers, vec!["column_1", "column_2"]);
+        assert_eq!(records[0], vec!["a", "1"]);
+        assert_eq!(records[1], vec!["b", "2", "extra"]);
+    }
+
+    #[test]
+    fn preview_csv_reads_only_the_first_rows() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("preview.csv");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "id").unwrap();
+        for i in 0..100 {
+            writeln!(file, "{}", i).unwrap();
+        }
+
+        let (headers, records) =
+            preview_csv(&file_path, &LoadOptions::default(), 5, &mut Vec::new()).unwrap();
+
+        assert_eq!(headers, vec!["id"]);
+        assert_eq!(records.len(), 5);
+    }
+
+    #[test]
+    fn headerless_file_round_trips_without_generated_names() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("headerless.csv");
+        let output = dir.path().join("headerless_sorted.csv");
+        let mut file = fs::File::create(&input).unwrap();
+        writeln!(file, "Carol,41").unwrap();
+        writeln!(file, "Alice,30").unwrap();
+        writeln!(file, "Bob,25").unwrap();
+
+        let options = LoadOptions::from(detect_dialect(&input));
+        let mut timings = PipelineTimings::default();
+        let Loaded {
+            headers,
+            rows: records,
+            ..
+        } = load_csv_timed(&input, Some(&options)).unwrap();
+        assert_eq!(headers, vec!["column_1", "column_2"]);
+        assert_eq!(records.len(), 3);
+
+        save_csv_timed(
+            &output,
+            &headers,
+            &records,
+            &ExportOptions::from(&options),
+            &mut timings,
+        )
+        .unwrap();
+        let content = fs::read_to_string(&output).unwrap();
+        assert_eq!(content.lines().next(), Some("Carol,41"));
+    }
+
     #[test]
     fn save_csv_writes_well_formed_file() {
         let dir = TempDir::new().unwrap();
@@ -248,4 +1171,30 @@ mod tests {
         assert_eq!(lines[1], "a,1");
         assert_eq!(lines[2], "b,2");
     }
+
+    #[test]
+    fn save_csv_timed_records_serialize_and_write_times() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("output.csv");
+        let headers = vec!["col1".to_string()];
+        let records = vec![vec!["a".to_string()]];
+        // Sentinels: a coarse clock may measure 0 ms, so only check they were set.
+        let mut timings = PipelineTimings {
+            serialize_ms: -1.0,
+            write_ms: -1.0,
+            ..PipelineTimings::default()
+        };
+
+        save_csv_timed(
+            &file_path,
+            &headers,
+            &records,
+            &ExportOptions::default(),
+            &mut timings,
+        )
+        .unwrap();
+
+        assert!(timings.serialize_ms >= 0.0);
+        assert!(timings.write_ms >= 0.0);
+    }
 }
@@ -0,0 +1,46 @@
+// Copyright (c) 2026 Neil Pandya
+
+//! Sorting algorithms, loaders, comparators and the benchmark harness behind
+//! the `csv-sort-bench` CLI and GUI.
+//!
+//! - [`io`] loads and saves tables (CSV, with [`readers`] and [`writers`] for
+//!   other formats), sniffing the [`dialect`], [`encoding`] and [`compression`].
+//! - [`models`] holds the row type and the error type used throughout.
+//! - [`algorithms`] has the sorts and their comparators; [`sort_keys`] builds
+//!   multi-column comparators.
+//! - [`bench`](mod@bench) runs the benchmarks, [`pipeline`] times the stages around the
+//!   sort, [`report`], [`history`] and [`regression`] record and compare the
+//!   results, and [`suite`] runs a whole matrix from a TOML file.
+//!
+//! Allocation stats stay zero unless the binary installs
+//! [`memory::CountingAllocator`] as its global allocator.
+//!
+//! The egui front-end is in [`gui`], behind the `gui` feature.
+
+pub mod algorithms;
+pub mod arena;
+pub(crate) mod atomic;
+pub mod bench;
+pub mod compression;
+pub(crate) mod diagnostics;
+pub mod dialect;
+pub mod encoding;
+pub(crate) mod environment;
+#[cfg(feature = "gui")]
+pub mod gui;
+pub mod history;
+pub mod io;
+pub mod memory;
+pub mod models;
+pub mod pipeline;
+pub mod readers;
+pub mod regression;
+pub mod report;
+pub mod sort_keys;
+pub mod suite;
+pub mod writers;
+
+// The allocation tests need the counting allocator installed.
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: memory::CountingAllocator = memory::CountingAllocator;
@@ -1,10 +1,23 @@
 // Copyright (c) 2026 Neil Pandya
 
-mod algorithms;
-mod gui;
-mod io;
-mod models;
+// Headless command-line binary; the GUI is `csv-sort-bench-gui`.
 
-fn main() -> eframe::Result<()> {
-    gui::run_app()
+mod cli;
+
+use clap::Parser;
+use csv_sort_bench::memory;
+use std::process::ExitCode;
+
+// Counts nothing unless a benchmark opts in via `memory::measure_allocations`.
+#[global_allocator]
+static GLOBAL: memory::CountingAllocator = memory::CountingAllocator;
+
+fn main() -> ExitCode {
+    match cli::run(cli::Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,215 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Memory probes: process-wide RSS from Linux procfs (quietly `None` elsewhere)
+// and a counting allocator for per-algorithm allocation stats.
+
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs;
+
+/// Resets the kernel's peak-RSS counter (`VmHWM`) for this process so the next
+/// `peak_rss_bytes` call measures only what happens after this point.
+pub fn reset_peak_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// Peak resident set size of this process, in bytes.
+pub fn peak_rss_bytes() -> Option<u64> {
+    read_status_kib("VmHWM:").map(|kib| kib * 1024)
+}
+
+/// Current resident set size of this process, in bytes.
+pub fn current_rss_bytes() -> Option<u64> {
+    read_status_kib("VmRSS:").map(|kib| kib * 1024)
+}
+
+fn read_status_kib(field: &str) -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    parse_status_kib(&status, field)
+}
+
+fn parse_status_kib(status: &str, field: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+/// Heap activity observed while a measured closure ran on the current thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Total bytes requested, including the new size of every reallocation.
+    pub bytes_allocated: u64,
+    /// Number of allocations and reallocations.
+    pub allocations: u64,
+    /// Highest net growth of live heap bytes above the starting point.
+    pub peak_live_bytes: u64,
+}
+
+/// Wraps the system allocator and, only while `measure_allocations` is running
+/// on the calling thread, counts that thread's allocations. The library doesn't
+/// install it; a binary that wants allocation stats declares it with
+/// `#[global_allocator]`, as both of ours do. Only the counting is opt-in: once
+/// installed, every allocation still pays a thread-local check of the tracking flag.
+pub struct CountingAllocator;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn is_tracking() -> bool {
+    // `try_with` fails during thread teardown; nothing is measured then.
+    TRACKING.try_with(Cell::get).unwrap_or(false)
+}
+
+fn record_alloc(size: usize) {
+    if !is_tracking() {
+        return;
+    }
+    ALLOCATED.set(ALLOCATED.get() + size as u64);
+    COUNT.set(COUNT.get() + 1);
+    record_live(size as i64);
+}
+
+fn record_dealloc(size: usize) {
+    if is_tracking() {
+        record_live(-(size as i64));
+    }
+}
+
+fn record_live(delta: i64) {
+    let live = LIVE.get() + delta;
+    LIVE.set(live);
+    PEAK.set(PEAK.get().max(live));
+}
+
+// SAFETY: every call is forwarded unchanged to `System`; the bookkeeping only
+// touches const-initialised thread-local `Cell`s, which never allocate.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` with allocation counting enabled on the current thread.
+/// Requires `CountingAllocator` to be the global allocator; otherwise the
+/// returned stats are all zero.
+pub fn measure_allocations<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    ALLOCATED.set(0);
+    COUNT.set(0);
+    LIVE.set(0);
+    PEAK.set(0);
+
+    let tracking = TrackingGuard::start();
+    let result = f();
+    drop(tracking);
+
+    let stats = AllocStats {
+        bytes_allocated: ALLOCATED.get(),
+        allocations: COUNT.get(),
+        peak_live_bytes: PEAK.get().max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// Turns counting on for the current thread until dropped, so a panicking
+/// closure doesn't leave it on.
+struct TrackingGuard;
+
+impl TrackingGuard {
+    fn start() -> Self {
+        TRACKING.set(true);
+        TrackingGuard
+    }
+}
+
+impl Drop for TrackingGuard {
+    fn drop(&mut self) {
+        TRACKING.set(false);
+    }
+}
+
+/// Formats a byte count as a human-readable MiB/KiB string.
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kib_fields_from_status() {
+        let status = "Name:\tcsv-sort-bench\nVmHWM:\t   20480 kB\nVmRSS:\t   10240 kB\n";
+
+        assert_eq!(parse_status_kib(status, "VmHWM:"), Some(20480));
+        assert_eq!(parse_status_kib(status, "VmRSS:"), Some(10240));
+        assert_eq!(parse_status_kib(status, "VmSwap:"), None);
+    }
+
+    #[test]
+    fn measure_allocations_counts_only_inside_the_closure() {
+        let before: Vec<u64> = Vec::with_capacity(64);
+
+        let (len, stats) = measure_allocations(|| {
+            let v: Vec<u64> = Vec::with_capacity(128);
+            v.capacity()
+        });
+        drop(before);
+
+        assert_eq!(len, 128);
+        assert_eq!(stats.allocations, 1);
+        assert_eq!(stats.bytes_allocated, 128 * 8);
+        assert_eq!(stats.peak_live_bytes, 128 * 8);
+    }
+
+    #[test]
+    fn a_panicking_closure_stops_the_counting() {
+        let result = std::panic::catch_unwind(|| measure_allocations(|| panic!("boom")));
+
+        assert!(result.is_err());
+        assert!(!is_tracking());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
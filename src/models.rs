@@ -1,5 +1,9 @@
 // Copyright (c) 2026 Neil Pandya
 
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
 /// This alias allows the codebase to remain agnostic of the underlying
 /// storage container, making future optimizations (like using Box or SmallVec) easier.
 pub type Record = Vec<String>;
@@ -8,21 +12,172 @@ pub type Record = Vec<String>;
 pub use crate::algorithms::BenchResult;
 
 /// Define error types.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum CsvError {
-    FileNotFound(String),
-    ParseError(String),
-    IoError(String),
+    FileNotFound {
+        path: PathBuf,
+    },
+    /// Malformed CSV or invalid UTF-8. Positions are filled in whenever the
+    /// reader knows them; `path` is `None` for in-memory input.
+    Parse {
+        path: Option<PathBuf>,
+        /// 1-based line number of the offending record.
+        line: Option<u64>,
+        /// Byte offset of the offending record from the start of the input.
+        byte: Option<u64>,
+        /// 0-based index of the offending field within the record; displayed 1-based.
+        field: Option<usize>,
+        message: String,
+        source: Option<csv::Error>,
+    },
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// An output format's own encoder failed, e.g. SQLite rejecting the table.
+    Export {
+        path: Option<PathBuf>,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl CsvError {
+    /// A parse error found outside the `csv` reader, e.g. while validating a row.
+    pub fn parse(line: u64, field: Option<usize>, message: String) -> Self {
+        CsvError::Parse {
+            path: None,
+            line: Some(line),
+            byte: None,
+            field,
+            message,
+            source: None,
+        }
+    }
+
+    /// Attaches the file the error came from, unless one is already set.
+    pub fn with_path(mut self, file: &Path) -> Self {
+        match &mut self {
+            CsvError::Parse { path, .. }
+            | CsvError::Io { path, .. }
+            | CsvError::Export { path, .. } => {
+                path.get_or_insert_with(|| file.to_path_buf());
+            }
+            CsvError::FileNotFound { .. } => {}
+        }
+        self
+    }
+}
+
+/// The error for a format this build leaves out, naming the Cargo feature
+/// that adds it.
+#[cfg(not(all(
+    feature = "parquet",
+    feature = "sqlite",
+    feature = "zstd",
+    feature = "bzip2"
+)))]
+pub(crate) fn unsupported(format: &str, feature: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "{} support is not built in; enable the `{}` feature",
+            format, feature
+        ),
+    )
+}
+
+impl From<io::Error> for CsvError {
+    fn from(source: io::Error) -> Self {
+        CsvError::Io { path: None, source }
+    }
 }
 
-impl std::fmt::Display for CsvError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl From<csv::Error> for CsvError {
+    fn from(error: csv::Error) -> Self {
+        if error.is_io_error() {
+            let csv::ErrorKind::Io(source) = error.into_kind() else {
+                unreachable!("is_io_error() checked the kind");
+            };
+            return source.into();
+        }
+        let position = error.position();
+        let line = position.map(|p| p.line());
+        let byte = position.map(|p| p.byte());
+        let (field, message) = match error.kind() {
+            // The csv crate's own messages repeat the position (and a 0-based
+            // field), which `Display` already prints.
+            csv::ErrorKind::Utf8 { err, .. } => (
+                Some(err.field()),
+                format!(
+                    "invalid UTF-8 after byte {} of the field",
+                    err.valid_up_to()
+                ),
+            ),
+            csv::ErrorKind::UnequalLengths {
+                expected_len, len, ..
+            } => (None, format!("{} fields, expected {}", len, expected_len)),
+            csv::ErrorKind::Deserialize { err, .. } => {
+                (err.field().map(|f| f as usize), err.kind().to_string())
+            }
+            _ => (None, error.to_string()),
+        };
+        CsvError::Parse {
+            path: None,
+            line,
+            byte,
+            field,
+            message,
+            source: Some(error),
+        }
+    }
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            CsvError::FileNotFound(path) => write!(f, "File not found: {}", path),
-            CsvError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-            CsvError::IoError(msg) => write!(f, "IO error: {}", msg),
+            CsvError::FileNotFound { path } => write!(f, "File not found: {}", path.display()),
+            CsvError::Parse {
+                path,
+                line,
+                byte,
+                field,
+                message,
+                ..
+            } => {
+                write!(f, "Parse error")?;
+                if let Some(path) = path {
+                    write!(f, " in {}", path.display())?;
+                }
+                if let Some(line) = line {
+                    write!(f, " at line {}", line)?;
+                }
+                if let Some(byte) = byte {
+                    write!(f, ", byte {}", byte)?;
+                }
+                if let Some(field) = field {
+                    write!(f, ", field {}", field + 1)?;
+                }
+                write!(f, ": {}", message)
+            }
+            CsvError::Io { path, source } => match path {
+                Some(path) => write!(f, "IO error on {}: {}", path.display(), source),
+                None => write!(f, "IO error: {}", source),
+            },
+            CsvError::Export { path, source } => match path {
+                Some(path) => write!(f, "Export error on {}: {}", path.display(), source),
+                None => write!(f, "Export error: {}", source),
+            },
         }
     }
 }
 
-impl std::error::Error for CsvError {}
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::FileNotFound { .. } => None,
+            CsvError::Parse { source, .. } => source.as_ref().map(|e| e as _),
+            CsvError::Io { source, .. } => Some(source),
+            CsvError::Export { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
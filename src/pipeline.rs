@@ -0,0 +1,172 @@
+// Copyright (c) 2026 Neil Pandya
+
+// End-to-end pipeline instrumentation: read → parse → key extraction → sort
+// → serialize → write, each stage timed separately.
+
+use crate::algorithms::{apply_permutation, row_indices};
+use crate::models::Record;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+/// Wall time spent in each pipeline stage, in milliseconds.
+/// Stages that did not run in the current session stay at zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineTimings {
+    pub read_ms: f64,
+    pub parse_ms: f64,
+    pub key_extraction_ms: f64,
+    pub sort_ms: f64,
+    pub serialize_ms: f64,
+    pub write_ms: f64,
+}
+
+impl PipelineTimings {
+    pub fn stages(&self) -> [(&'static str, f64); 6] {
+        [
+            ("read", self.read_ms),
+            ("parse", self.parse_ms),
+            ("key extraction", self.key_extraction_ms),
+            ("sort", self.sort_ms),
+            ("serialize", self.serialize_ms),
+            ("write", self.write_ms),
+        ]
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.stages().iter().map(|(_, ms)| ms).sum()
+    }
+}
+
+impl fmt::Display for PipelineTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, ms) in self.stages() {
+            write!(f, "{} {:.2} | ", name, ms)?;
+        }
+        write!(f, "total {:.2} ms", self.total_ms())
+    }
+}
+
+/// Wraps a reader or writer and adds up the time spent inside its calls, so a
+/// streamed load or export can still be split into its I/O and processing stages.
+pub struct Timed<T> {
+    inner: T,
+    elapsed: Duration,
+}
+
+impl<T> Timed<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Time spent inside `read`/`write`/`flush` so far.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl<R: Read> Read for Timed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let start = Instant::now();
+        let result = self.inner.read(buf);
+        self.elapsed += start.elapsed();
+        result
+    }
+}
+
+impl<W: Write> Write for Timed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let start = Instant::now();
+        let result = self.inner.write(buf);
+        self.elapsed += start.elapsed();
+        result
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let start = Instant::now();
+        let result = self.inner.flush();
+        self.elapsed += start.elapsed();
+        result
+    }
+}
+
+/// A row's sort key, parsed once up front instead of on every comparison.
+type SortKey<'a> = (Option<f64>, &'a str);
+
+fn extract_keys(records: &[Record], column_index: usize) -> Vec<SortKey<'_>> {
+    records
+        .iter()
+        .map(|r| {
+            let value = r.get(column_index).map(|s| s.as_str()).unwrap_or("");
+            (value.parse::<f64>().ok(), value)
+        })
+        .collect()
+}
+
+/// Same ordering as `algorithms::compare_values`, on pre-parsed keys.
+fn compare_keys(a: &SortKey, b: &SortKey) -> Ordering {
+    match (a.0, b.0) {
+        (Some(num_a), Some(num_b)) => num_a.partial_cmp(&num_b).unwrap_or(Ordering::Equal),
+        _ => a.1.cmp(b.1),
+    }
+}
+
+/// Sorts `records` by extracting every key once, sorting row indices on those
+/// keys and applying the permutation. Records the key extraction and sort stages.
+pub fn sort_with_keys(records: &mut [Record], column_index: usize, timings: &mut PipelineTimings) {
+    let start = Instant::now();
+    let keys = extract_keys(records, column_index);
+    timings.key_extraction_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let start = Instant::now();
+    let mut indices = row_indices(records);
+    indices.sort_by(|&a, &b| compare_keys(&keys[a as usize], &keys[b as usize]));
+    drop(keys);
+    apply_permutation(records, &indices);
+    timings.sort_ms = start.elapsed().as_secs_f64() * 1000.0;
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::Algorithm;
+
+    #[test]
+    fn sort_with_keys_matches_standard_sort() {
+        let mut data: Vec<Record> = vec![
+            vec!["b".into(), "10".into()],
+            vec!["a".into(), "9".into()],
+            vec!["c".into(), "x".into()],
+            vec!["d".into(), "".into()],
+        ];
+        let mut expected = data.clone();
+        let mut timings = PipelineTimings::default();
+
+        Algorithm::Std.sort(&mut expected, 1);
+        sort_with_keys(&mut data, 1, &mut timings);
+
+        assert_eq!(data, expected);
+        assert!(timings.sort_ms >= 0.0);
+    }
+
+    #[test]
+    fn total_sums_every_stage() {
+        let timings = PipelineTimings {
+            read_ms: 1.0,
+            parse_ms: 2.0,
+            key_extraction_ms: 0.5,
+            sort_ms: 3.0,
+            serialize_ms: 1.5,
+            write_ms: 2.0,
+        };
+
+        assert_eq!(timings.total_ms(), 10.0);
+        assert!(timings.to_string().ends_with("total 10.00 ms"));
+    }
+}
@@ -0,0 +1,433 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Non-CSV inputs: JSON Lines, Parquet and fixed-width text, each turned into
+// the same `(headers, rows)` shape as a CSV load (see `io::TableReader`).
+
+use crate::compression::Compression;
+use crate::diagnostics::{RowAction, RowIssue};
+use crate::encoding;
+use crate::io::TableReader;
+use crate::models::{CsvError, Record};
+#[cfg(feature = "parquet")]
+use arrow_array::RecordBatchReader;
+#[cfg(feature = "parquet")]
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+#[cfg(feature = "parquet")]
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// The kinds of file the loaders understand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Any delimiter, including TSV; the dialect is sniffed.
+    #[default]
+    Csv,
+    Ndjson,
+    Parquet,
+    FixedWidth,
+}
+
+impl InputFormat {
+    pub const ALL: [InputFormat; 4] = [
+        InputFormat::Csv,
+        InputFormat::Ndjson,
+        InputFormat::Parquet,
+        InputFormat::FixedWidth,
+    ];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputFormat::Csv => "csv",
+            InputFormat::Ndjson => "ndjson",
+            InputFormat::Parquet => "parquet",
+            InputFormat::FixedWidth => "fixed-width",
+        }
+    }
+
+    /// Guesses from the extension, looking through a compression suffix
+    /// (`events.jsonl.gz` is NDJSON). Fixed-width files have no telltale
+    /// extension, so they must be asked for explicitly.
+    pub fn from_path(path: &Path) -> Self {
+        let path = match Compression::from_path(path) {
+            Compression::None => path.to_path_buf(),
+            _ => path.with_extension(""),
+        };
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "jsonl" | "ndjson" => InputFormat::Ndjson,
+            "parquet" => InputFormat::Parquet,
+            _ => InputFormat::Csv,
+        }
+    }
+}
+
+/// JSON Lines: one object per line, its top-level keys flattened to columns
+/// in first-seen order. Nested values are kept as compact JSON text.
+pub struct NdjsonReader;
+
+impl TableReader for NdjsonReader {
+    fn read_table(
+        &self,
+        input: &mut dyn Read,
+        _issues: &mut Vec<RowIssue>,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        let mut input = BufReader::new(encoding::decode_reader(input, encoding_rs::UTF_8));
+        let mut headers: Vec<String> = Vec::new();
+        let mut objects = Vec::new();
+        let mut line = Vec::new();
+        let mut line_number = 0;
+        loop {
+            line.clear();
+            if input.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            line_number += 1;
+            if line.trim_ascii().is_empty() {
+                continue;
+            }
+            let object = match serde_json::from_slice::<Value>(&line) {
+                Ok(Value::Object(object)) => object,
+                Ok(_) => {
+                    return Err(CsvError::parse(
+                        line_number,
+                        None,
+                        "expected a JSON object".to_string(),
+                    ));
+                }
+                Err(e) => return Err(CsvError::parse(line_number, None, e.to_string())),
+            };
+            for key in object.keys() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+            objects.push(object);
+        }
+
+        let records = objects
+            .iter()
+            .map(|object| {
+                headers
+                    .iter()
+                    .map(|key| match object.get(key) {
+                        None | Some(Value::Null) => String::new(),
+                        Some(Value::String(s)) => s.clone(),
+                        Some(value) => value.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok((headers, records))
+    }
+}
+
+/// Apache Parquet, read through Arrow; every value is rendered as text and
+/// nulls become empty cells. Needs the `parquet` feature.
+pub struct ParquetReader;
+
+#[cfg(not(feature = "parquet"))]
+impl TableReader for ParquetReader {
+    fn read_table(
+        &self,
+        _input: &mut dyn Read,
+        _issues: &mut Vec<RowIssue>,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        Err(crate::models::unsupported("Parquet", "parquet").into())
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl TableReader for ParquetReader {
+    fn read_table(
+        &self,
+        input: &mut dyn Read,
+        _issues: &mut Vec<RowIssue>,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        let parse_error = |e: &dyn std::fmt::Display| CsvError::Parse {
+            path: None,
+            line: None,
+            byte: None,
+            field: None,
+            message: format!("Parquet: {}", e),
+            source: None,
+        };
+        // Parquet's footer sits at the end of the file, so it has to be read
+        // whole; the buffer is handed over rather than copied.
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes))
+            .and_then(|builder| builder.build())
+            .map_err(|e| parse_error(&e))?;
+        let headers = reader
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+
+        let options = FormatOptions::default().with_null("");
+        let mut records = Vec::new();
+        for batch in reader {
+            let batch = batch.map_err(|e| parse_error(&e))?;
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| parse_error(&e))?;
+            for row in 0..batch.num_rows() {
+                records.push(
+                    columns
+                        .iter()
+                        .map(|column| column.value(row).to_string())
+                        .collect(),
+                );
+            }
+        }
+        Ok((headers, records))
+    }
+}
+
+/// Fixed-width text: each column is a run of characters of a set width,
+/// in order, with surrounding spaces trimmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedWidthReader {
+    /// `(name, width in characters)` for each column, left to right.
+    pub columns: Vec<(String, usize)>,
+    /// Skip the first line (a header row the spec already names).
+    pub skip_header: bool,
+}
+
+impl FixedWidthReader {
+    /// Parses a column spec like `id:4,name:12,age:3`. Names must be
+    /// non-empty and distinct, since they become the headers.
+    pub fn from_spec(spec: &str, skip_header: bool) -> Result<Self, String> {
+        let columns: Vec<(String, usize)> = spec
+            .split(',')
+            .map(|column| {
+                let (name, width) = column
+                    .split_once(':')
+                    .ok_or_else(|| format!("expected name:width, got {:?}", column))?;
+                let width = width
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&w| w > 0)
+                    .ok_or_else(|| format!("invalid width in {:?}", column))?;
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(format!("missing column name in {:?}", column));
+                }
+                Ok((name.to_string(), width))
+            })
+            .collect::<Result<_, String>>()?;
+        for (i, (name, _)) in columns.iter().enumerate() {
+            if columns[..i].iter().any(|(earlier, _)| earlier == name) {
+                return Err(format!("column {:?} is named twice", name));
+            }
+        }
+        Ok(Self {
+            columns,
+            skip_header,
+        })
+    }
+}
+
+impl FixedWidthReader {
+    /// `read_table`, stopping after `max_rows` rows; e.g. for a preview.
+    pub fn read_rows(
+        &self,
+        input: &mut dyn Read,
+        issues: &mut Vec<RowIssue>,
+        max_rows: usize,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        let (sample, input) = crate::io::peek(input, crate::dialect::SNIFF_SAMPLE_BYTES)?;
+        let input = BufReader::new(encoding::decode_reader(input, encoding::detect(&sample).0));
+
+        let headers = self.columns.iter().map(|(name, _)| name.clone()).collect();
+        let line_width: usize = self.columns.iter().map(|&(_, width)| width).sum();
+        let mut records = Vec::new();
+        let lines = input.split(b'\n').zip(1u64..);
+        for (line, line_number) in lines.skip(usize::from(self.skip_header)) {
+            if records.len() >= max_rows {
+                break;
+            }
+            let line = String::from_utf8(line?).map_err(|e| {
+                let message = format!(
+                    "invalid UTF-8 after byte {} of the line",
+                    e.utf8_error().valid_up_to()
+                );
+                CsvError::parse(line_number, None, message)
+            })?;
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let length = line.chars().count();
+            if length != line_width {
+                issues.push(RowIssue {
+                    line: line_number,
+                    reason: format!("expected {} characters, found {}", line_width, length),
+                    action: if length < line_width {
+                        RowAction::Padded
+                    } else {
+                        RowAction::Truncated
+                    },
+                });
+            }
+            let mut chars = line.chars();
+            records.push(
+                self.columns
+                    .iter()
+                    .map(|&(_, width)| {
+                        let cell: String = chars.by_ref().take(width).collect();
+                        cell.trim().to_string()
+                    })
+                    .collect(),
+            );
+        }
+        Ok((headers, records))
+    }
+}
+
+impl TableReader for FixedWidthReader {
+    fn read_table(
+        &self,
+        input: &mut dyn Read,
+        issues: &mut Vec<RowIssue>,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        self.read_rows(input, issues, usize::MAX)
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "parquet")]
+    use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    #[cfg(feature = "parquet")]
+    use parquet::arrow::ArrowWriter;
+    #[cfg(feature = "parquet")]
+    use std::sync::Arc;
+
+    #[test]
+    fn flattens_ndjson_keys_in_first_seen_order() {
+        let input = b"{\"name\":\"Alice\",\"age\":30}\n\n{\"age\":25,\"name\":\"Bob\",\"tags\":[\"x\"],\"note\":null}\n";
+
+        let (headers, records) = NdjsonReader
+            .read_table(&mut &input[..], &mut Vec::new())
+            .unwrap();
+
+        assert_eq!(headers, vec!["name", "age", "tags", "note"]);
+        assert_eq!(records[0], vec!["Alice", "30", "", ""]);
+        assert_eq!(records[1], vec!["Bob", "25", "[\"x\"]", ""]);
+    }
+
+    #[test]
+    fn reports_the_line_of_bad_ndjson() {
+        let input = b"{\"a\":1}\n[1,2]\n";
+
+        match NdjsonReader.read_table(&mut &input[..], &mut Vec::new()) {
+            Err(CsvError::Parse { line, .. }) => assert_eq!(line, Some(2)),
+            other => panic!("Expected Parse error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parquet")]
+    fn reads_parquet_columns_as_text() {
+        let names: ArrayRef = Arc::new(StringArray::from(vec![Some("Alice"), None]));
+        let ages: ArrayRef = Arc::new(Int64Array::from(vec![30, 25]));
+        let batch = RecordBatch::try_from_iter([("name", names), ("age", ages)]).unwrap();
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let (headers, records) = ParquetReader
+            .read_table(&mut &bytes[..], &mut Vec::new())
+            .unwrap();
+
+        assert_eq!(headers, vec!["name", "age"]);
+        assert_eq!(records, vec![vec!["Alice", "30"], vec!["", "25"]]);
+    }
+
+    #[test]
+    fn splits_fixed_width_lines_by_the_spec() {
+        let reader = FixedWidthReader::from_spec("id:3, name:8,age:3", true).unwrap();
+        let input = "ID NAME    AGE\n1  Alice   30\n2  Zoë     7\n3  Bob\n";
+
+        let (headers, records) = reader
+            .read_table(&mut input.as_bytes(), &mut Vec::new())
+            .unwrap();
+
+        assert_eq!(headers, vec!["id", "name", "age"]);
+        assert_eq!(records[0], vec!["1", "Alice", "30"]);
+        assert_eq!(records[1], vec!["2", "Zoë", "7"]);
+        assert_eq!(records[2], vec!["3", "Bob", ""]);
+        assert!(FixedWidthReader::from_spec("id:0", false).is_err());
+        assert!(FixedWidthReader::from_spec("id", false).is_err());
+        assert!(FixedWidthReader::from_spec(":4", false).is_err());
+        assert!(FixedWidthReader::from_spec("name:4,name:3", false).is_err());
+    }
+
+    #[test]
+    fn reports_fixed_width_lines_of_the_wrong_length() {
+        let reader = FixedWidthReader::from_spec("id:2,name:4", false).unwrap();
+        let mut issues = Vec::new();
+
+        let (_, records) = reader
+            .read_table(&mut &b"1 Ann \n2 Bo\n3 Carla\n"[..], &mut issues)
+            .unwrap();
+
+        assert_eq!(records[2], vec!["3", "Carl"]);
+        let lines: Vec<(u64, RowAction)> = issues.iter().map(|i| (i.line, i.action)).collect();
+        assert_eq!(
+            lines,
+            vec![(2, RowAction::Padded), (3, RowAction::Truncated)]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_utf8_in_fixed_width_lines() {
+        let reader = FixedWidthReader::from_spec("id:2,name:4", false).unwrap();
+        // Past the sniffed sample, so the file is taken to be UTF-8.
+        let lines = crate::dialect::SNIFF_SAMPLE_BYTES / 7 + 1;
+        let mut input = b"1 Ann \n".repeat(lines);
+        input.extend_from_slice(b"2 B\xff  \n");
+
+        match reader.read_table(&mut &input[..], &mut Vec::new()) {
+            Err(CsvError::Parse { line, .. }) => assert_eq!(line, Some(lines as u64 + 1)),
+            other => panic!("Expected Parse error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn reads_only_the_first_fixed_width_rows() {
+        let reader = FixedWidthReader::from_spec("id:2", true).unwrap();
+        let input = "id\n1\n2\n3\n";
+
+        let (_, records) = reader
+            .read_rows(&mut input.as_bytes(), &mut Vec::new(), 2)
+            .unwrap();
+
+        assert_eq!(records, vec![vec!["1"], vec!["2"]]);
+    }
+
+    #[test]
+    fn picks_the_format_from_the_extension() {
+        assert_eq!(
+            InputFormat::from_path(Path::new("a.jsonl.gz")),
+            InputFormat::Ndjson
+        );
+        assert_eq!(
+            InputFormat::from_path(Path::new("a.parquet")),
+            InputFormat::Parquet
+        );
+        assert_eq!(InputFormat::from_path(Path::new("a.tsv")), InputFormat::Csv);
+    }
+}
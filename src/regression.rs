@@ -0,0 +1,408 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Baseline regression checks for CI: compares a run against a saved JSON
+// report and flags every algorithm that slowed down beyond its tolerance.
+
+use crate::history::{self, Delta};
+use crate::report::BenchReport;
+use std::fmt::Write as _;
+
+/// How much slower than the baseline an algorithm may get. A slowdown only
+/// counts as a regression when it exceeds both limits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub abs_ms: f64,
+    pub rel_pct: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            abs_ms: 0.0,
+            rel_pct: 10.0,
+        }
+    }
+}
+
+impl Tolerance {
+    /// Applies an override such as `5%` or `2ms` (or `2.5 ms`) on top of `self`.
+    pub fn with_limit(mut self, limit: &str) -> Result<Self, String> {
+        let limit = limit.trim();
+        let number = |text: &str| {
+            text.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| *value >= 0.0)
+                .ok_or_else(|| format!("invalid tolerance: {:?}", limit))
+        };
+        if let Some(pct) = limit.strip_suffix('%') {
+            self.rel_pct = number(pct)?;
+        } else if let Some(ms) = limit.strip_suffix("ms") {
+            self.abs_ms = number(ms)?;
+        } else {
+            return Err(format!("tolerance must end in % or ms, got {:?}", limit));
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RegressionConfig {
+    pub default: Tolerance,
+    /// Per-algorithm overrides, by result name (e.g. `Quick Sort (index)`).
+    pub per_algorithm: Vec<(String, Tolerance)>,
+    /// When set, a slowdown must also be significant at this level under a
+    /// one-sided Mann-Whitney U test of the trial samples.
+    pub significance: Option<f64>,
+}
+
+impl RegressionConfig {
+    pub fn tolerance(&self, name: &str) -> Tolerance {
+        self.per_algorithm
+            .iter()
+            .rev()
+            .find(|(algorithm, _)| algorithm == name)
+            .map_or(self.default, |&(_, tolerance)| tolerance)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Faster,
+    Regressed,
+    /// Only in the current run.
+    New,
+    /// Only in the baseline.
+    Missing,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Faster => "faster",
+            Status::Regressed => "REGRESSED",
+            Status::New => "new",
+            Status::Missing => "missing",
+        }
+    }
+}
+
+/// The verdict for one algorithm.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub delta: Delta,
+    pub tolerance: Tolerance,
+    /// Probability of a slowdown at least this large by chance, when both runs
+    /// have at least two samples.
+    pub p_value: Option<f64>,
+    pub status: Status,
+}
+
+/// Why a baseline's timings say nothing about `current`: it sorted another
+/// file, a table of another shape, or another column.
+fn dataset_mismatch(baseline: &BenchReport, current: &BenchReport) -> Option<String> {
+    let (base, other) = (&baseline.dataset, &current.dataset);
+    if base.path != other.path {
+        Some(format!(
+            "it was measured on {:?}, not {:?}",
+            base.path, other.path
+        ))
+    } else if (base.rows, base.columns) != (other.rows, other.columns) {
+        Some(format!(
+            "{:?} had {} rows and {} columns then but has {} rows and {} columns now",
+            other.path, base.rows, base.columns, other.rows, other.columns
+        ))
+    } else if baseline.column != current.column {
+        Some(format!(
+            "it sorted by {:?}, not {:?}",
+            baseline.column, current.column
+        ))
+    } else {
+        None
+    }
+}
+
+/// Checks every algorithm of `current` against `baseline`; fails if the two
+/// runs came from builds with different profiles or sorted different data.
+pub fn check(
+    baseline: &BenchReport,
+    current: &BenchReport,
+    config: &RegressionConfig,
+) -> Result<Vec<Check>, String> {
+    if let Some(reason) =
+        history::profile_mismatch(baseline, current).or_else(|| dataset_mismatch(baseline, current))
+    {
+        return Err(format!("baseline: {}", reason));
+    }
+    let samples = |report: &BenchReport, name: &str| {
+        report
+            .results
+            .iter()
+            .find(|result| result.name == name)
+            .map(|result| result.samples_ms.clone())
+            .unwrap_or_default()
+    };
+
+    Ok(history::compare(baseline, current)
+        .into_iter()
+        .map(|delta| {
+            let tolerance = config.tolerance(&delta.name);
+            let p_value = mann_whitney_p(
+                &samples(baseline, &delta.name),
+                &samples(current, &delta.name),
+            );
+            let status = match (delta.base_ms, delta.other_ms) {
+                (None, _) => Status::New,
+                (_, None) => Status::Missing,
+                (Some(base), Some(current)) => {
+                    let slower_ms = current - base;
+                    let slower_pct = delta.percent().unwrap_or(0.0);
+                    let significant = match (config.significance, p_value) {
+                        (Some(alpha), Some(p)) => p < alpha,
+                        _ => true,
+                    };
+                    if slower_ms > tolerance.abs_ms && slower_pct > tolerance.rel_pct && significant
+                    {
+                        Status::Regressed
+                    } else if slower_ms < 0.0 {
+                        Status::Faster
+                    } else {
+                        Status::Ok
+                    }
+                }
+            };
+            Check {
+                delta,
+                tolerance,
+                p_value,
+                status,
+            }
+        })
+        .collect())
+}
+
+/// One-sided Mann-Whitney U test that `current` tends to be slower than
+/// `base`, using the normal approximation with a continuity correction.
+pub fn mann_whitney_p(base: &[f64], current: &[f64]) -> Option<f64> {
+    if base.len() < 2 || current.len() < 2 {
+        return None;
+    }
+    let u: f64 = current
+        .iter()
+        .flat_map(|&c| base.iter().map(move |&b| (c, b)))
+        .map(|(c, b)| match c.partial_cmp(&b) {
+            Some(std::cmp::Ordering::Greater) => 1.0,
+            Some(std::cmp::Ordering::Equal) => 0.5,
+            _ => 0.0,
+        })
+        .sum();
+    let (n1, n2) = (base.len() as f64, current.len() as f64);
+    let mean = n1 * n2 / 2.0;
+    let sd = (n1 * n2 * (n1 + n2 + 1.0) / 12.0).sqrt();
+    let z = (u - mean - 0.5) / sd;
+    Some(1.0 - normal_cdf(z))
+}
+
+fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Abramowitz & Stegun 7.1.26; accurate to about 1.5e-7.
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let y = 1.0 - poly * (-x * x).exp();
+    if x < 0.0 { -y } else { y }
+}
+
+/// A table of every check, for the terminal or a CI log.
+pub fn format_checks(checks: &[Check]) -> String {
+    let ms = |value: Option<f64>| value.map_or("-".to_string(), |ms| format!("{:.3}", ms));
+    let mut text = format!(
+        "{:<28} {:>12} {:>12} {:>9} {:>9} {:>18}  {}\n",
+        "Algorithm", "Base (ms)", "Now (ms)", "Change", "p-value", "Tolerance", "Status"
+    );
+    for check in checks {
+        let _ = writeln!(
+            text,
+            "{:<28} {:>12} {:>12} {:>9} {:>9} {:>18}  {}",
+            check.delta.name,
+            ms(check.delta.base_ms),
+            ms(check.delta.other_ms),
+            check
+                .delta
+                .percent()
+                .map_or("-".to_string(), |pct| format!("{:+.1}%", pct)),
+            check
+                .p_value
+                .map_or("-".to_string(), |p| format!("{:.3}", p)),
+            format!(
+                "{:.3}ms, {:.1}%",
+                check.tolerance.abs_ms, check.tolerance.rel_pct
+            ),
+            check.status.name()
+        );
+    }
+    text
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::BenchResult;
+    use crate::environment::Environment;
+    use crate::pipeline::PipelineTimings;
+    use crate::report::{BenchParams, DatasetInfo};
+
+    fn report(results: &[(&str, &[f64])]) -> BenchReport {
+        BenchReport {
+            dataset: DatasetInfo::default(),
+            column: "age".to_string(),
+            params: BenchParams::default(),
+            results: results
+                .iter()
+                .map(|&(name, samples)| BenchResult {
+                    name: name.to_string(),
+                    duration_ms: crate::bench::median(samples),
+                    alloc_stats: None,
+                    samples_ms: samples.to_vec(),
+                    skipped: false,
+                })
+                .collect(),
+            pipeline: PipelineTimings::default(),
+            environment: Environment::default(),
+        }
+    }
+
+    fn statuses(checks: &[Check]) -> Vec<Status> {
+        checks.iter().map(|check| check.status).collect()
+    }
+
+    #[test]
+    fn flags_slowdowns_beyond_both_tolerances() {
+        let baseline = report(&[
+            ("Std Sort", &[10.0]),
+            ("Merge Sort", &[10.0]),
+            ("Quick Sort", &[10.0]),
+            ("Bubble Sort", &[1.0]),
+        ]);
+        let current = report(&[
+            ("Std Sort", &[12.0]),
+            ("Merge Sort", &[10.5]),
+            ("Quick Sort", &[8.0]),
+            ("Insertion Sort", &[1.0]),
+        ]);
+        let config = RegressionConfig {
+            default: Tolerance::default().with_limit("1ms").unwrap(),
+            ..Default::default()
+        };
+
+        let checks = check(&baseline, &current, &config).unwrap();
+
+        assert_eq!(
+            statuses(&checks),
+            vec![
+                Status::Regressed,
+                Status::Ok,
+                Status::Faster,
+                Status::Missing,
+                Status::New
+            ]
+        );
+    }
+
+    #[test]
+    fn per_algorithm_tolerances_override_the_default() {
+        let baseline = report(&[("Std Sort", &[10.0]), ("Merge Sort", &[10.0])]);
+        let current = report(&[("Std Sort", &[12.0]), ("Merge Sort", &[12.0])]);
+        let config = RegressionConfig {
+            per_algorithm: vec![(
+                "Merge Sort".to_string(),
+                Tolerance::default().with_limit("25%").unwrap(),
+            )],
+            ..Default::default()
+        };
+
+        let checks = check(&baseline, &current, &config).unwrap();
+
+        assert_eq!(statuses(&checks), vec![Status::Regressed, Status::Ok]);
+        assert!(Tolerance::default().with_limit("5").is_err());
+    }
+
+    #[test]
+    fn significance_test_ignores_noisy_slowdowns() {
+        let noisy_base: &[f64] = &[10.0, 14.0, 9.0, 13.0, 11.0];
+        let noisy_now: &[f64] = &[12.0, 9.5, 13.5, 10.5, 14.5];
+        let baseline = report(&[
+            ("Std Sort", noisy_base),
+            ("Quick Sort", &[10.0, 10.2, 9.9, 10.1]),
+        ]);
+        let current = report(&[
+            ("Std Sort", noisy_now),
+            ("Quick Sort", &[13.0, 13.1, 12.9, 13.2]),
+        ]);
+        let config = RegressionConfig {
+            default: Tolerance {
+                abs_ms: 0.0,
+                rel_pct: 0.0,
+            },
+            significance: Some(0.05),
+            ..Default::default()
+        };
+
+        let checks = check(&baseline, &current, &config).unwrap();
+
+        assert_eq!(statuses(&checks), vec![Status::Ok, Status::Regressed]);
+        assert!(checks[0].p_value.unwrap() > 0.05);
+        assert!(checks[1].p_value.unwrap() < 0.05);
+        assert_eq!(mann_whitney_p(&[1.0], &[2.0, 3.0]), None);
+    }
+
+    #[test]
+    fn refuses_a_baseline_from_another_build_profile() {
+        let mut baseline = report(&[("Std Sort", &[10.0])]);
+        let current = report(&[("Std Sort", &[10.0])]);
+        baseline.environment.profile = "release".to_string();
+
+        assert!(check(&baseline, &current, &RegressionConfig::default()).is_ok());
+        let mut current = current;
+        current.environment.profile = "debug".to_string();
+        assert!(check(&baseline, &current, &RegressionConfig::default()).is_err());
+    }
+
+    #[test]
+    fn refuses_a_baseline_for_other_data() {
+        let baseline = report(&[("Std Sort", &[10.0])]);
+        let config = RegressionConfig::default();
+
+        let mut current = report(&[("Std Sort", &[10.0])]);
+        current.dataset.path = "other.csv".to_string();
+        assert!(
+            check(&baseline, &current, &config)
+                .unwrap_err()
+                .contains("other.csv")
+        );
+
+        let mut current = report(&[("Std Sort", &[10.0])]);
+        current.dataset.rows = 1000;
+        assert!(
+            check(&baseline, &current, &config)
+                .unwrap_err()
+                .contains("1000 rows")
+        );
+
+        let mut current = report(&[("Std Sort", &[10.0])]);
+        current.column = "name".to_string();
+        assert!(
+            check(&baseline, &current, &config)
+                .unwrap_err()
+                .contains("\"name\"")
+        );
+    }
+}
@@ -0,0 +1,369 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Benchmark reports: one run's results together with what they were measured
+// on, exportable as CSV, JSON or a Markdown table.
+
+use crate::algorithms::BenchResult;
+use crate::atomic;
+use crate::bench::BenchOptions;
+pub use crate::environment::Environment;
+use crate::memory::AllocStats;
+use crate::models::CsvError;
+use crate::pipeline::PipelineTimings;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    #[default]
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [
+        ReportFormat::Csv,
+        ReportFormat::Json,
+        ReportFormat::Markdown,
+    ];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Markdown => "markdown",
+        }
+    }
+
+    /// File extension without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Markdown => "md",
+        }
+    }
+
+    /// Guesses from the extension; anything unrecognised is CSV.
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => ReportFormat::Json,
+            "md" | "markdown" => ReportFormat::Markdown,
+            _ => ReportFormat::Csv,
+        }
+    }
+}
+
+/// The file a run was measured on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatasetInfo {
+    pub path: String,
+    /// Input format name, e.g. `csv` or `parquet`.
+    pub format: String,
+    pub rows: usize,
+    pub columns: usize,
+}
+
+/// The `BenchOptions` a run used, without the borrowed arena.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchParams {
+    pub index_sort: bool,
+    pub arena: bool,
+    pub track_allocations: bool,
+    /// Runs per algorithm; reports from before trials existed read as 1.
+    #[serde(default = "one")]
+    pub trials: usize,
+}
+
+fn one() -> usize {
+    1
+}
+
+impl From<&BenchOptions<'_>> for BenchParams {
+    fn from(options: &BenchOptions) -> Self {
+        Self {
+            index_sort: options.compare_index_sort,
+            arena: options.arena.is_some(),
+            track_allocations: options.track_allocations,
+            trials: options.trials.max(1),
+        }
+    }
+}
+
+/// Everything needed to interpret one benchmark run later or elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub dataset: DatasetInfo,
+    /// Header of the column the rows were sorted by.
+    pub column: String,
+    pub params: BenchParams,
+    pub results: Vec<BenchResult>,
+    /// Stage timings of the load and the keyed sort/serialize that followed.
+    pub pipeline: PipelineTimings,
+    /// Build and machine the run was measured on; empty in older reports.
+    #[serde(default)]
+    pub environment: Environment,
+}
+
+impl BenchReport {
+    pub fn render(&self, format: ReportFormat) -> Result<Vec<u8>, CsvError> {
+        match format {
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Json => {
+                let mut bytes = serde_json::to_vec_pretty(self)
+                    .map_err(|e| CsvError::from(std::io::Error::from(e)))?;
+                bytes.push(b'\n');
+                Ok(bytes)
+            }
+            ReportFormat::Markdown => Ok(self.to_markdown().into_bytes()),
+        }
+    }
+
+    /// Writes the report to `path` atomically.
+    pub fn save(&self, path: &Path, format: ReportFormat) -> Result<(), CsvError> {
+        let bytes = self.render(format)?;
+        atomic::write_atomic(path, &bytes, false, |_| Ok(()))
+            .map_err(|e| CsvError::from(e).with_path(path))
+    }
+
+    /// Reads a report saved as JSON, e.g. a baseline from an earlier run.
+    pub fn load(path: &Path) -> Result<Self, CsvError> {
+        let bytes = std::fs::read(path).map_err(|e| CsvError::from(e).with_path(path))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| CsvError::from(std::io::Error::from(e)).with_path(path))
+    }
+
+    /// One row per result, repeating the run's metadata so rows from several
+    /// reports can be concatenated and compared.
+    fn to_csv(&self) -> Result<Vec<u8>, CsvError> {
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        wtr.write_record([
+            "dataset",
+            "format",
+            "rows",
+            "columns",
+            "column",
+            "index_sort",
+            "arena",
+            "track_allocations",
+            "trials",
+            "algorithm",
+            "duration_ms",
+            "bytes_allocated",
+            "allocations",
+            "peak_live_bytes",
+            "crate_version",
+            "git_commit",
+            "profile",
+            "rustc",
+            "cpu_model",
+            "cpu_cores",
+            "memory_bytes",
+            "os",
+            "kernel",
+            "timestamp",
+        ])?;
+        let env = &self.environment;
+        for result in self.results.iter().filter(|result| !result.skipped) {
+            let stats = result.alloc_stats;
+            let stat = |value: fn(&AllocStats) -> u64| {
+                stats
+                    .as_ref()
+                    .map(value)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            };
+            wtr.write_record([
+                self.dataset.path.clone(),
+                self.dataset.format.clone(),
+                self.dataset.rows.to_string(),
+                self.dataset.columns.to_string(),
+                self.column.clone(),
+                self.params.index_sort.to_string(),
+                self.params.arena.to_string(),
+                self.params.track_allocations.to_string(),
+                self.params.trials.to_string(),
+                result.name.clone(),
+                format!("{:.3}", result.duration_ms),
+                stat(|s| s.bytes_allocated),
+                stat(|s| s.allocations),
+                stat(|s| s.peak_live_bytes),
+                env.crate_version.clone(),
+                env.git_commit.clone(),
+                env.profile.clone(),
+                env.rustc.clone(),
+                env.cpu_model.clone(),
+                env.cpu_cores.to_string(),
+                env.memory_bytes.to_string(),
+                env.os.clone(),
+                env.kernel.clone(),
+                env.timestamp.clone(),
+            ])?;
+        }
+        wtr.into_inner().map_err(|e| CsvError::from(e.into_error()))
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = format!(
+            "## Benchmark results{}\n\n\
+             - Dataset: {} ({}, {} rows x {} columns)\n\
+             - Sorted by: {}\n\
+             - Index sort: {}, arena: {}, allocation tracking: {}, trials: {}\n\n",
+            if self.environment.is_debug() {
+                " (debug build)"
+            } else {
+                ""
+            },
+            self.dataset.path,
+            self.dataset.format,
+            self.dataset.rows,
+            self.dataset.columns,
+            self.column,
+            self.params.index_sort,
+            self.params.arena,
+            self.params.track_allocations,
+            self.params.trials,
+        );
+
+        let tracked = self.params.track_allocations;
+        text.push_str("| Algorithm | Time (ms) |");
+        if tracked {
+            text.push_str(" Allocated (bytes) | Allocs | Peak live (bytes) |");
+        }
+        text.push_str("\n| --- | ---: |");
+        if tracked {
+            text.push_str(" ---: | ---: | ---: |");
+        }
+        text.push('\n');
+        for result in self.results.iter().filter(|result| !result.skipped) {
+            text.push_str(&format!(
+                "| {} | {:.3} |",
+                result.name.replace('|', "\\|"),
+                result.duration_ms
+            ));
+            if tracked {
+                let stats = result.alloc_stats.unwrap_or_default();
+                text.push_str(&format!(
+                    " {} | {} | {} |",
+                    stats.bytes_allocated, stats.allocations, stats.peak_live_bytes
+                ));
+            }
+            text.push('\n');
+        }
+
+        text.push_str("\n| Pipeline stage | Time (ms) |\n| --- | ---: |\n");
+        for (stage, ms) in self.pipeline.stages() {
+            text.push_str(&format!("| {} | {:.3} |\n", stage, ms));
+        }
+        text.push_str(&format!("| total | {:.3} |\n", self.pipeline.total_ms()));
+
+        text.push_str("\n### Environment\n\n");
+        for (label, value) in self.environment.fields() {
+            text.push_str(&format!("- {}: {}\n", label, value));
+        }
+        text
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> BenchReport {
+        BenchReport {
+            dataset: DatasetInfo {
+                path: "students.csv".to_string(),
+                format: "csv".to_string(),
+                rows: 100,
+                columns: 3,
+            },
+            column: "age".to_string(),
+            params: BenchParams {
+                track_allocations: true,
+                trials: 1,
+                ..BenchParams::default()
+            },
+            results: vec![
+                BenchResult {
+                    name: "Std Sort".to_string(),
+                    duration_ms: 1.25,
+                    alloc_stats: Some(AllocStats {
+                        bytes_allocated: 4096,
+                        allocations: 2,
+                        peak_live_bytes: 4096,
+                    }),
+                    samples_ms: Vec::new(),
+                    skipped: false,
+                },
+                BenchResult {
+                    name: "Merge Sort".to_string(),
+                    duration_ms: 2.5,
+                    alloc_stats: None,
+                    samples_ms: Vec::new(),
+                    skipped: false,
+                },
+            ],
+            pipeline: PipelineTimings::default(),
+            environment: Environment {
+                crate_version: "0.1.0".to_string(),
+                profile: "release".to_string(),
+                cpu_cores: 8,
+                ..Environment::default()
+            },
+        }
+    }
+
+    #[test]
+    fn renders_one_csv_row_per_result_with_the_run_metadata() {
+        let csv = String::from_utf8(report().render(ReportFormat::Csv).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "students.csv,csv,100,3,age,false,false,true,1,Std Sort,1.250,4096,2,4096,0.1.0,,release,,,8,0,,,"
+        );
+        assert_eq!(
+            lines[2],
+            "students.csv,csv,100,3,age,false,false,true,1,Merge Sort,2.500,,,,0.1.0,,release,,,8,0,,,"
+        );
+    }
+
+    #[test]
+    fn renders_json_and_markdown() {
+        let json: serde_json::Value =
+            serde_json::from_slice(&report().render(ReportFormat::Json).unwrap()).unwrap();
+        let markdown = String::from_utf8(report().render(ReportFormat::Markdown).unwrap()).unwrap();
+
+        assert_eq!(json["dataset"]["rows"], 100);
+        assert_eq!(json["results"][0]["alloc_stats"]["allocations"], 2);
+        assert_eq!(json["results"][1]["alloc_stats"], serde_json::Value::Null);
+        assert!(markdown.contains("| Std Sort | 1.250 | 4096 | 2 | 4096 |\n"));
+        assert!(markdown.contains("| total | 0.000 |\n"));
+        assert_eq!(json["environment"]["profile"], "release");
+        assert!(markdown.contains("- Profile: release\n- Compiler: unknown\n"));
+        assert!(markdown.starts_with("## Benchmark results\n"));
+    }
+
+    #[test]
+    fn leaves_skipped_placeholders_out_of_csv_and_markdown() {
+        let mut report = report();
+        report
+            .results
+            .push(BenchResult::skipped("Bubble Sort".to_string()));
+
+        let csv = String::from_utf8(report.render(ReportFormat::Csv).unwrap()).unwrap();
+        let markdown = String::from_utf8(report.render(ReportFormat::Markdown).unwrap()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(&report.render(ReportFormat::Json).unwrap()).unwrap();
+
+        assert_eq!(csv.lines().count(), 3);
+        assert!(!markdown.contains("Bubble Sort"));
+        assert_eq!(json["results"][2]["skipped"], true);
+        assert_eq!(json["results"][0].get("skipped"), None);
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Multi-column sort keys for the `sort` command: `age:num:desc`-style specs
+// resolved against the headers, with numeric or text comparison per key.
+
+use crate::io;
+use crate::models::Record;
+use std::cmp::Ordering;
+
+/// How a key's cells are compared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyType {
+    /// Numeric if every non-empty cell of the column is a number, else text.
+    #[default]
+    Auto,
+    /// Numeric order, with any cells that aren't numbers last, as text.
+    Number,
+    /// Plain string order.
+    Text,
+}
+
+/// A key as typed on the command line: `COLUMN[:num|:text][:asc|:desc]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySpec {
+    /// Header name or zero-based index.
+    pub column: String,
+    pub key_type: KeyType,
+    pub descending: bool,
+}
+
+impl KeySpec {
+    /// Only trailing segments that are modifiers are taken off, so a header
+    /// such as `time:utc` still names a column.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut spec = KeySpec {
+            column: text.to_string(),
+            key_type: KeyType::Auto,
+            descending: false,
+        };
+        while let Some((rest, modifier)) = spec.column.rsplit_once(':') {
+            match modifier {
+                "num" if spec.key_type == KeyType::Auto => spec.key_type = KeyType::Number,
+                "text" if spec.key_type == KeyType::Auto => spec.key_type = KeyType::Text,
+                "asc" => {}
+                "desc" => spec.descending = true,
+                _ => break,
+            }
+            spec.column = rest.to_string();
+        }
+        if spec.column.is_empty() {
+            return Err(format!("sort key {:?} names no column", text));
+        }
+        Ok(spec)
+    }
+
+    /// Looks up the column and settles an `Auto` type from the data.
+    pub fn resolve(&self, headers: &[String], records: &[Record]) -> Result<SortKey, String> {
+        let column = io::resolve_column(headers, &self.column).ok_or_else(|| {
+            format!(
+                "column {:?} not found; available: {}",
+                self.column,
+                headers.join(", ")
+            )
+        })?;
+        let key_type = match self.key_type {
+            KeyType::Auto => infer_key_type(records, column),
+            key_type => key_type,
+        };
+        Ok(SortKey {
+            column,
+            key_type,
+            descending: self.descending,
+        })
+    }
+}
+
+/// `Number` if every non-empty cell of `column`, trimmed, is a finite number,
+/// as `SortKey::compare` parses it; otherwise `Text`. Unlike an export's
+/// column types, `1.50` or `02139` count as numbers here.
+fn infer_key_type(records: &[Record], column: usize) -> KeyType {
+    let numeric = records
+        .iter()
+        .filter_map(|record| record.get(column).map(|cell| cell.trim()))
+        .filter(|cell| !cell.is_empty())
+        .all(|cell| cell.parse::<f64>().is_ok_and(f64::is_finite));
+    if numeric {
+        KeyType::Number
+    } else {
+        KeyType::Text
+    }
+}
+
+/// A resolved key: a column index and a concrete (non-`Auto`) type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: usize,
+    pub key_type: KeyType,
+    pub descending: bool,
+}
+
+impl SortKey {
+    fn compare(&self, a: &Record, b: &Record) -> Ordering {
+        let a = a.get(self.column).map_or("", String::as_str);
+        let b = b.get(self.column).map_or("", String::as_str);
+        let ordering = match self.key_type {
+            KeyType::Text => a.cmp(b),
+            KeyType::Number | KeyType::Auto => {
+                match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                    (Ok(a), Ok(b)) => a.total_cmp(&b),
+                    // Blanks and other non-numbers stay last in either direction.
+                    (Ok(_), Err(_)) => return Ordering::Less,
+                    (Err(_), Ok(_)) => return Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                }
+            }
+        };
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Compares rows key by key, the first difference deciding.
+pub fn compare_rows(keys: &[SortKey]) -> impl Fn(&Record, &Record) -> Ordering + '_ {
+    move |a, b| {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> Vec<String> {
+        ["name", "age", "time:utc"].map(String::from).to_vec()
+    }
+
+    fn rows(cells: &[[&str; 3]]) -> Vec<Record> {
+        cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_modifiers_off_the_end_only() {
+        let spec = |text| KeySpec::parse(text).unwrap();
+
+        assert_eq!(spec("age:num:desc").key_type, KeyType::Number);
+        assert!(spec("age:num:desc").descending);
+        assert_eq!(spec("age:desc").column, "age");
+        assert_eq!(spec("time:utc").column, "time:utc");
+        assert_eq!(spec("time:utc:text").column, "time:utc");
+        assert_eq!(spec("a:text:num").column, "a:text");
+        assert!(KeySpec::parse(":desc").is_err());
+    }
+
+    #[test]
+    fn sorts_by_several_typed_keys() {
+        let mut records = rows(&[
+            ["Cara", "9", "b"],
+            ["Abe", "10", "a"],
+            ["Bea", "10", "c"],
+            ["Dov", "", "d"],
+        ]);
+        let keys = [
+            KeySpec::parse("age:desc").unwrap(),
+            KeySpec::parse("name").unwrap(),
+        ]
+        .iter()
+        .map(|spec| spec.resolve(&headers(), &records))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+        assert_eq!(keys[0].key_type, KeyType::Number);
+        assert_eq!(keys[1].key_type, KeyType::Text);
+        records.sort_by(compare_rows(&keys));
+        let names: Vec<&str> = records.iter().map(|r| r[0].as_str()).collect();
+        // Descending numbers, ties by name, the empty age (not a number) last.
+        assert_eq!(names, vec!["Abe", "Bea", "Cara", "Dov"]);
+
+        let text = KeySpec::parse("age:text")
+            .unwrap()
+            .resolve(&headers(), &records)
+            .unwrap();
+        records.sort_by(compare_rows(&[text]));
+        let ages: Vec<&str> = records.iter().map(|r| r[1].as_str()).collect();
+        assert_eq!(ages, vec!["", "10", "10", "9"]);
+        assert!(
+            KeySpec::parse("salary")
+                .unwrap()
+                .resolve(&headers(), &records)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn sorts_decimals_and_leading_zeros_as_numbers() {
+        let sorted = |cells: &[&str]| {
+            let mut records: Vec<Record> = cells.iter().map(|c| vec![c.to_string()]).collect();
+            let key = KeySpec::parse("0")
+                .unwrap()
+                .resolve(&["v".into()], &records)
+                .unwrap();
+            assert_eq!(key.key_type, KeyType::Number);
+            records.sort_by(compare_rows(&[key]));
+            records
+                .into_iter()
+                .map(|r| r[0].clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sorted(&["10.25", "2.00", "1.50", "3"]),
+            vec!["1.50", "2.00", "3", "10.25"]
+        );
+        assert_eq!(
+            sorted(&["10001", " 9001", "02139"]),
+            vec!["02139", " 9001", "10001"]
+        );
+    }
+
+    #[test]
+    fn treats_infinite_or_mixed_columns_as_text() {
+        let records = vec![vec!["1".to_string()], vec!["inf".to_string()]];
+        let key = KeySpec::parse("0")
+            .unwrap()
+            .resolve(&["v".into()], &records)
+            .unwrap();
+        assert_eq!(key.key_type, KeyType::Text);
+    }
+}
@@ -0,0 +1,885 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Declarative benchmark suites: a TOML file lists datasets (files or
+// generated data), sort columns, input distributions and algorithms, and the
+// whole matrix runs in one go into a combined report.
+
+use crate::algorithms::{self, Algorithm, BenchResult};
+use crate::arena::RecordArena;
+use crate::atomic;
+use crate::bench::{self, BUBBLE_SORT_ROW_LIMIT};
+use crate::dialect::LoadOptions;
+use crate::environment::Environment;
+use crate::io::{self, TableReader};
+use crate::memory::AllocStats;
+use crate::models::{CsvError, Record};
+use crate::pipeline::PipelineTimings;
+use crate::readers::{FixedWidthReader, InputFormat, NdjsonReader, ParquetReader};
+use crate::report::{BenchParams, BenchReport, DatasetInfo, ReportFormat};
+use fake::Fake;
+use fake::faker::name::en::Name;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A parsed suite file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    #[serde(default)]
+    pub name: String,
+    /// Runs per algorithm and variant; the report keeps the median.
+    #[serde(default = "one")]
+    pub trials: usize,
+    /// Time budget per algorithm and variant across its trials. Sorts can't be
+    /// interrupted, so the trial that crosses it finishes and the rest are
+    /// skipped.
+    pub timeout_ms: Option<f64>,
+    /// Seeds the data generator and the shuffled distributions.
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(rename = "dataset")]
+    pub datasets: Vec<DatasetSpec>,
+    /// Every algorithm with default parameters when empty.
+    #[serde(rename = "algorithm", default)]
+    pub algorithms: Vec<AlgorithmSpec>,
+}
+
+fn one() -> usize {
+    1
+}
+
+/// A file to load or data to generate; exactly one of `path` and `generate`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetSpec {
+    /// Label in the report; defaults to the file name or `generated`.
+    pub name: Option<String>,
+    /// Relative paths are resolved against the suite file's directory.
+    pub path: Option<PathBuf>,
+    /// Input format name, e.g. `ndjson`; defaults to the path's extension.
+    pub format: Option<String>,
+    /// Column spec for fixed-width input, e.g. `id:4,name:12,age:3`.
+    pub fixed_width: Option<String>,
+    pub generate: Option<GeneratorSpec>,
+    /// Columns to sort by, as header names or zero-based indices; the first
+    /// column when empty.
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// Row orders to benchmark; the data as loaded when empty.
+    #[serde(default)]
+    pub distributions: Vec<Distribution>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneratorSpec {
+    pub rows: usize,
+    pub columns: Vec<GeneratedColumn>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneratedColumn {
+    pub name: String,
+    pub kind: ColumnKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnKind {
+    /// 0..1,000,000
+    Int,
+    /// 0..1000 with three decimals
+    Float,
+    /// Eight random lowercase letters
+    Word,
+    /// A fake person's name
+    Name,
+}
+
+/// How the rows are ordered by the sort column before each run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Distribution {
+    #[default]
+    AsIs,
+    Random,
+    Sorted,
+    Reversed,
+    /// Sorted, then about 1% of the rows swapped.
+    NearlySorted,
+    /// Shuffled, with the sort column reduced to at most eight distinct values.
+    FewUnique,
+}
+
+impl Distribution {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::AsIs => "as-is",
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::NearlySorted => "nearly-sorted",
+            Distribution::FewUnique => "few-unique",
+        }
+    }
+
+    /// Reorders (and for `FewUnique`, rewrites) `records` by `column_index`.
+    pub fn arrange(&self, records: &mut [Record], column_index: usize, rng: &mut StdRng) {
+        let sort = |records: &mut [Record]| {
+            records.sort_by(algorithms::get_comparator(column_index));
+        };
+        match self {
+            Distribution::AsIs => {}
+            Distribution::Random => records.shuffle(rng),
+            Distribution::Sorted => sort(records),
+            Distribution::Reversed => {
+                sort(records);
+                records.reverse();
+            }
+            Distribution::NearlySorted => {
+                sort(records);
+                if records.len() > 1 {
+                    for _ in 0..(records.len() / 100).max(1) {
+                        let a = rng.gen_range(0..records.len());
+                        let b = rng.gen_range(0..records.len());
+                        records.swap(a, b);
+                    }
+                }
+            }
+            Distribution::FewUnique => {
+                let mut values: Vec<String> = records
+                    .iter()
+                    .filter_map(|record| record.get(column_index).cloned())
+                    .collect();
+                values.sort();
+                values.dedup();
+                values.shuffle(rng);
+                values.truncate(8);
+                if !values.is_empty() {
+                    for record in records.iter_mut() {
+                        if let Some(cell) = record.get_mut(column_index) {
+                            *cell = values[rng.gen_range(0..values.len())].clone();
+                        }
+                    }
+                }
+                records.shuffle(rng);
+            }
+        }
+    }
+}
+
+/// One algorithm and the variants and limits to run it with.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlgorithmSpec {
+    /// As in the results, e.g. `Quick Sort`.
+    pub name: String,
+    /// Also sort row indices instead of whole rows.
+    #[serde(default)]
+    pub index_sort: bool,
+    /// Also sort arena-backed storage.
+    #[serde(default)]
+    pub arena: bool,
+    #[serde(default)]
+    pub track_allocations: bool,
+    /// Skip datasets with more rows; see `default_max_rows` for the default.
+    pub max_rows: Option<usize>,
+    /// Overrides the suite's `timeout_ms`.
+    pub timeout_ms: Option<f64>,
+}
+
+impl AlgorithmSpec {
+    fn with_defaults(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            index_sort: false,
+            arena: false,
+            track_allocations: false,
+            max_rows: None,
+            timeout_ms: None,
+        }
+    }
+}
+
+/// `BUBBLE_SORT_ROW_LIMIT` wherever an algorithm is quadratic, otherwise no
+/// limit. Besides the slow algorithms, that is Lomuto quicksort on a column
+/// with many repeated keys, whatever the distribution: keys equal to the pivot
+/// all land on one side, so `c` equal keys cost about `c²` comparisons and
+/// recurse `c` deep. It is limited once `repeated_pairs` (see
+/// `repeated_key_pairs`) outgrows what a slow sort does at the row limit.
+fn default_max_rows(algorithm: &Algorithm, slow: bool, repeated_pairs: usize) -> Option<usize> {
+    let quadratic =
+        slow || (*algorithm == Algorithm::Quick && repeated_pairs > BUBBLE_SORT_ROW_LIMIT.pow(2));
+    quadratic.then_some(BUBBLE_SORT_ROW_LIMIT)
+}
+
+/// The number of ordered pairs of rows with the same cell in `column_index`:
+/// the sum of `c·(c−1)` over each distinct key occurring `c` times.
+fn repeated_key_pairs(records: &[Record], column_index: usize) -> usize {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for record in records {
+        let key = record.get(column_index).map_or("", String::as_str);
+        *counts.entry(key).or_default() += 1;
+    }
+    counts.values().map(|&count| count * (count - 1)).sum()
+}
+
+fn find_algorithm(name: &str) -> Option<(Algorithm, bool)> {
+    let fast = algorithms::FAST_ALGORITHMS.iter().map(|a| (*a, false));
+    let slow = algorithms::SLOW_ALGORITHMS.iter().map(|a| (*a, true));
+    fast.chain(slow)
+        .find(|(algorithm, _)| algorithm.name() == name)
+}
+
+fn invalid(path: &Path, message: String) -> CsvError {
+    CsvError::from(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    ))
+    .with_path(path)
+}
+
+impl Suite {
+    /// Reads and validates a suite file.
+    pub fn load(path: &Path) -> Result<Self, CsvError> {
+        let text = std::fs::read_to_string(path).map_err(|e| CsvError::from(e).with_path(path))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&text, base_dir).map_err(|message| invalid(path, message))
+    }
+
+    /// Parses suite TOML, resolving dataset paths against `base_dir`.
+    pub fn parse(text: &str, base_dir: &Path) -> Result<Self, String> {
+        let mut suite: Suite = toml::from_str(text).map_err(|e| e.to_string())?;
+        if suite.datasets.is_empty() {
+            return Err("a suite needs at least one [[dataset]]".to_string());
+        }
+        for (i, dataset) in suite.datasets.iter_mut().enumerate() {
+            match (&mut dataset.path, &dataset.generate) {
+                (Some(path), None) => *path = base_dir.join(&*path),
+                (None, Some(_)) => {}
+                _ => {
+                    return Err(format!(
+                        "dataset {} needs exactly one of `path` and `generate`",
+                        i + 1
+                    ));
+                }
+            }
+            dataset.input_format()?;
+        }
+        for algorithm in &suite.algorithms {
+            if find_algorithm(&algorithm.name).is_none() {
+                let names: Vec<&str> = algorithms::FAST_ALGORITHMS
+                    .iter()
+                    .chain(&algorithms::SLOW_ALGORITHMS)
+                    .map(|a| a.name())
+                    .collect();
+                return Err(format!(
+                    "unknown algorithm {:?}; expected one of {}",
+                    algorithm.name,
+                    names.join(", ")
+                ));
+            }
+        }
+        Ok(suite)
+    }
+
+    fn algorithm_specs(&self) -> Vec<AlgorithmSpec> {
+        if !self.algorithms.is_empty() {
+            return self.algorithms.clone();
+        }
+        algorithms::FAST_ALGORITHMS
+            .iter()
+            .chain(&algorithms::SLOW_ALGORITHMS)
+            .map(|algorithm| AlgorithmSpec::with_defaults(algorithm.name()))
+            .collect()
+    }
+
+    /// Runs every dataset × column × distribution × algorithm combination,
+    /// calling `progress` before each group.
+    pub fn run(&self, progress: &mut dyn FnMut(&str)) -> Result<SuiteReport, CsvError> {
+        let environment = Environment::capture();
+        let specs = self.algorithm_specs();
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut runs = Vec::new();
+
+        for dataset in &self.datasets {
+            let mut timings = PipelineTimings::default();
+            let (headers, records) = dataset.load(&mut rng, &mut timings)?;
+            let label = dataset.label();
+            let columns = match dataset.columns.as_slice() {
+                [] => vec!["0".to_string()],
+                columns => columns.to_vec(),
+            };
+            let distributions = match dataset.distributions.as_slice() {
+                [] => vec![Distribution::AsIs],
+                distributions => distributions.to_vec(),
+            };
+
+            for column in &columns {
+                let column_index = io::resolve_column(&headers, column).ok_or_else(|| {
+                    invalid(
+                        dataset.path.as_deref().unwrap_or(Path::new(&label)),
+                        format!("column {:?} not found in dataset {}", column, label),
+                    )
+                })?;
+                for &distribution in &distributions {
+                    progress(&format!(
+                        "{} / {} / {}",
+                        label,
+                        headers[column_index],
+                        distribution.name()
+                    ));
+                    let mut arranged = records.clone();
+                    distribution.arrange(&mut arranged, column_index, &mut rng);
+                    let results = self.run_group(&specs, &arranged, column_index);
+
+                    runs.push(SuiteRun {
+                        dataset: label.clone(),
+                        distribution,
+                        report: BenchReport {
+                            dataset: DatasetInfo {
+                                path: dataset
+                                    .path
+                                    .as_ref()
+                                    .map(|p| p.display().to_string())
+                                    .unwrap_or_default(),
+                                format: match dataset.generate {
+                                    Some(_) => "generated".to_string(),
+                                    None => dataset
+                                        .input_format()
+                                        .expect("validated in parse")
+                                        .name()
+                                        .to_string(),
+                                },
+                                rows: arranged.len(),
+                                columns: headers.len(),
+                            },
+                            column: headers[column_index].clone(),
+                            params: BenchParams {
+                                index_sort: specs.iter().any(|s| s.index_sort),
+                                arena: specs.iter().any(|s| s.arena),
+                                track_allocations: specs.iter().any(|s| s.track_allocations),
+                                trials: self.trials.max(1),
+                            },
+                            results,
+                            pipeline: timings,
+                            environment: environment.clone(),
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(SuiteReport {
+            name: self.name.clone(),
+            runs,
+        })
+    }
+
+    fn run_group(
+        &self,
+        specs: &[AlgorithmSpec],
+        records: &[Record],
+        column_index: usize,
+    ) -> Vec<BenchResult> {
+        let mut results = Vec::new();
+        let mut arena = None;
+        let repeated_pairs = repeated_key_pairs(records, column_index);
+        for spec in specs {
+            let (algorithm, slow) = find_algorithm(&spec.name).expect("validated in parse");
+            let max_rows = spec
+                .max_rows
+                .or(default_max_rows(&algorithm, slow, repeated_pairs))
+                .unwrap_or(usize::MAX);
+            if records.len() > max_rows {
+                // Same convention as `bench::run_benchmarks`: a zero entry and a note.
+                results.push(BenchResult::skipped(algorithm.name().to_string()));
+                results.push(BenchResult::skipped(format!(
+                    "({} skipped: >{} rows)",
+                    algorithm.name(),
+                    max_rows
+                )));
+                continue;
+            }
+
+            results.extend(self.run_trials(algorithm.name().to_string(), spec, || {
+                let mut data = records.to_vec();
+                move || algorithm.sort(&mut data, column_index)
+            }));
+            if spec.index_sort {
+                results.extend(self.run_trials(
+                    format!("{} (index)", algorithm.name()),
+                    spec,
+                    || {
+                        let mut data = records.to_vec();
+                        move || algorithm.sort_indices(&mut data, column_index)
+                    },
+                ));
+            }
+            if spec.arena {
+                let arena: &RecordArena =
+                    arena.get_or_insert_with(|| RecordArena::from_records(records));
+                results.extend(self.run_trials(
+                    format!("{} (arena)", algorithm.name()),
+                    spec,
+                    || {
+                        let mut data = arena.clone();
+                        move || algorithm.sort_arena(&mut data, column_index)
+                    },
+                ));
+            }
+        }
+        results
+    }
+
+    /// Runs up to `trials` fresh copies of one sort, stopping early once the
+    /// timeout is used up. `prepare` builds each trial's copy outside the timer.
+    fn run_trials<R: FnOnce() -> f64>(
+        &self,
+        name: String,
+        spec: &AlgorithmSpec,
+        prepare: impl Fn() -> R,
+    ) -> Vec<BenchResult> {
+        let trials = self.trials.max(1);
+        let timeout_ms = spec.timeout_ms.or(self.timeout_ms);
+        let mut samples = Vec::with_capacity(trials);
+        let mut alloc_stats: Option<AllocStats> = None;
+        let mut note = None;
+
+        for trial in 1..=trials {
+            let run = prepare();
+            // Allocation stats come from the first trial, as in `run_benchmarks`.
+            let result = bench::measure(name.clone(), spec.track_allocations && trial == 1, run);
+            samples.push(result.duration_ms);
+            alloc_stats = alloc_stats.or(result.alloc_stats);
+
+            if trial < trials && timeout_ms.is_some_and(|limit| samples.iter().sum::<f64>() > limit)
+            {
+                note = Some(BenchResult::skipped(format!(
+                    "({} timed out after {} of {} trials)",
+                    name, trial, trials
+                )));
+                break;
+            }
+        }
+
+        let mut result = BenchResult {
+            name,
+            duration_ms: bench::median(&samples),
+            alloc_stats,
+            samples_ms: Vec::new(),
+            skipped: false,
+        };
+        if trials > 1 {
+            result.samples_ms = samples;
+        }
+        std::iter::once(result).chain(note).collect()
+    }
+}
+
+impl DatasetSpec {
+    fn label(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        self.path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "generated".to_string())
+    }
+
+    fn input_format(&self) -> Result<InputFormat, String> {
+        match (&self.format, &self.fixed_width, &self.path) {
+            (Some(name), fixed_width, _) => {
+                let format = InputFormat::ALL
+                    .into_iter()
+                    .find(|format| format.name() == name)
+                    .ok_or_else(|| format!("unknown format {:?}", name))?;
+                if fixed_width.is_some() && format != InputFormat::FixedWidth {
+                    return Err(format!("`fixed_width` does not apply to {} input", name));
+                }
+                Ok(format)
+            }
+            (None, Some(_), _) => Ok(InputFormat::FixedWidth),
+            (None, None, Some(path)) => Ok(InputFormat::from_path(path)),
+            (None, None, None) => Ok(InputFormat::Csv),
+        }
+    }
+
+    fn load(
+        &self,
+        rng: &mut StdRng,
+        timings: &mut PipelineTimings,
+    ) -> Result<(Vec<String>, Vec<Record>), CsvError> {
+        let Some(path) = &self.path else {
+            let spec = self.generate.as_ref().expect("validated in parse");
+            return Ok(spec.generate(rng));
+        };
+        let format = self.input_format().map_err(|e| invalid(path, e))?;
+        let load_options;
+        let fixed_width;
+        let reader: &dyn TableReader = match format {
+            InputFormat::Csv => {
+                load_options = LoadOptions::from(io::detect_dialect(path));
+                &load_options
+            }
+            InputFormat::Ndjson => &NdjsonReader,
+            InputFormat::Parquet => &ParquetReader,
+            InputFormat::FixedWidth => {
+                let spec = self.fixed_width.as_deref().ok_or_else(|| {
+                    invalid(path, "fixed-width input needs `fixed_width`".to_string())
+                })?;
+                fixed_width =
+                    FixedWidthReader::from_spec(spec, false).map_err(|e| invalid(path, e))?;
+                &fixed_width
+            }
+        };
+        let loaded = io::load_table_timed(path, reader)?;
+        *timings = loaded.timings;
+        Ok((loaded.headers, loaded.rows))
+    }
+}
+
+impl GeneratorSpec {
+    pub fn generate(&self, rng: &mut StdRng) -> (Vec<String>, Vec<Record>) {
+        let headers = self.columns.iter().map(|c| c.name.clone()).collect();
+        let records = (0..self.rows)
+            .map(|_| {
+                self.columns
+                    .iter()
+                    .map(|column| match column.kind {
+                        ColumnKind::Int => rng.gen_range(0..1_000_000).to_string(),
+                        ColumnKind::Float => format!("{:.3}", rng.gen_range(0.0..1000.0)),
+                        ColumnKind::Word => (0..8).map(|_| rng.gen_range('a'..='z')).collect(),
+                        ColumnKind::Name => Name().fake_with_rng(rng),
+                    })
+                    .collect()
+            })
+            .collect();
+        (headers, records)
+    }
+}
+
+/// One dataset × column × distribution group of a suite run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SuiteRun {
+    pub dataset: String,
+    pub distribution: Distribution,
+    pub report: BenchReport,
+}
+
+/// The combined results of a suite.
+#[derive(Debug, Clone, Serialize)]
+pub struct SuiteReport {
+    pub name: String,
+    pub runs: Vec<SuiteRun>,
+}
+
+impl SuiteReport {
+    pub fn render(&self, format: ReportFormat) -> Result<Vec<u8>, CsvError> {
+        match format {
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Json => {
+                let mut bytes = serde_json::to_vec_pretty(self)
+                    .map_err(|e| CsvError::from(std::io::Error::from(e)))?;
+                bytes.push(b'\n');
+                Ok(bytes)
+            }
+            ReportFormat::Markdown => Ok(self.to_markdown().into_bytes()),
+        }
+    }
+
+    /// Writes the report to `path` atomically.
+    pub fn save(&self, path: &Path, format: ReportFormat) -> Result<(), CsvError> {
+        let bytes = self.render(format)?;
+        atomic::write_atomic(path, &bytes, false, |_| Ok(()))
+            .map_err(|e| CsvError::from(e).with_path(path))
+    }
+
+    /// Every result of every group as `(run, result)`, without the skipped
+    /// placeholders.
+    pub fn rows(&self) -> impl Iterator<Item = (&SuiteRun, &BenchResult)> {
+        self.runs.iter().flat_map(|run| {
+            run.report
+                .results
+                .iter()
+                .filter(|result| !result.skipped)
+                .map(move |result| (run, result))
+        })
+    }
+
+    fn to_csv(&self) -> Result<Vec<u8>, CsvError> {
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        wtr.write_record([
+            "suite",
+            "dataset",
+            "distribution",
+            "rows",
+            "column",
+            "trials",
+            "algorithm",
+            "duration_ms",
+            "min_ms",
+            "max_ms",
+            "bytes_allocated",
+            "allocations",
+            "peak_live_bytes",
+            "git_commit",
+            "profile",
+        ])?;
+        for (run, result) in self.rows() {
+            let (min, max) = spread(result);
+            let stat = |value: fn(&AllocStats) -> u64| {
+                result
+                    .alloc_stats
+                    .as_ref()
+                    .map(value)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            };
+            wtr.write_record([
+                self.name.clone(),
+                run.dataset.clone(),
+                run.distribution.name().to_string(),
+                run.report.dataset.rows.to_string(),
+                run.report.column.clone(),
+                run.report.params.trials.to_string(),
+                result.name.clone(),
+                format!("{:.3}", result.duration_ms),
+                format!("{:.3}", min),
+                format!("{:.3}", max),
+                stat(|s| s.bytes_allocated),
+                stat(|s| s.allocations),
+                stat(|s| s.peak_live_bytes),
+                run.report.environment.git_commit.clone(),
+                run.report.environment.profile.clone(),
+            ])?;
+        }
+        wtr.into_inner().map_err(|e| CsvError::from(e.into_error()))
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = format!(
+            "## Suite results{}\n\n",
+            match self.name.as_str() {
+                "" => String::new(),
+                name => format!(": {}", name),
+            }
+        );
+        if let Some(run) = self.runs.first() {
+            for (label, value) in run.report.environment.fields() {
+                text.push_str(&format!("- {}: {}\n", label, value));
+            }
+            text.push('\n');
+        }
+        text.push_str(
+            "| Dataset | Distribution | Rows | Column | Algorithm | Median (ms) | Min (ms) | Max (ms) |\n\
+             | --- | --- | ---: | --- | --- | ---: | ---: | ---: |\n",
+        );
+        for (run, result) in self.rows() {
+            let (min, max) = spread(result);
+            text.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {:.3} | {:.3} | {:.3} |\n",
+                run.dataset.replace('|', "\\|"),
+                run.distribution.name(),
+                run.report.dataset.rows,
+                run.report.column.replace('|', "\\|"),
+                result.name.replace('|', "\\|"),
+                result.duration_ms,
+                min,
+                max
+            ));
+        }
+        text
+    }
+}
+
+/// Fastest and slowest trial, or the single time when only one ran.
+fn spread(result: &BenchResult) -> (f64, f64) {
+    let samples = &result.samples_ms;
+    if samples.is_empty() {
+        return (result.duration_ms, result.duration_ms);
+    }
+    let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (min, max)
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SUITE: &str = r#"
+        name = "smoke"
+        trials = 3
+        seed = 7
+
+        [[dataset]]
+        name = "people"
+        columns = ["age", "name"]
+        distributions = ["random", "reversed", "few-unique"]
+        [dataset.generate]
+        rows = 50
+        columns = [
+            { name = "name", kind = "name" },
+            { name = "age", kind = "int" },
+        ]
+
+        [[algorithm]]
+        name = "Quick Sort"
+        index_sort = true
+
+        [[algorithm]]
+        name = "Bubble Sort"
+        max_rows = 10
+    "#;
+
+    fn ages(records: &[Record]) -> Vec<i64> {
+        records.iter().map(|r| r[0].parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn runs_every_combination_into_one_report() {
+        let suite = Suite::parse(SUITE, Path::new("")).unwrap();
+        let mut groups = Vec::new();
+
+        let report = suite
+            .run(&mut |group| groups.push(group.to_string()))
+            .unwrap();
+
+        assert_eq!(report.runs.len(), 6);
+        assert_eq!(groups[0], "people / age / random");
+        let names: Vec<&str> = report.runs[0]
+            .report
+            .results
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Quick Sort",
+                "Quick Sort (index)",
+                "Bubble Sort",
+                "(Bubble Sort skipped: >10 rows)"
+            ]
+        );
+        assert_eq!(report.runs[0].report.results[0].samples_ms.len(), 3);
+        assert_eq!(report.runs[0].report.params.trials, 3);
+
+        let csv = String::from_utf8(report.render(ReportFormat::Csv).unwrap()).unwrap();
+        let markdown = String::from_utf8(report.render(ReportFormat::Markdown).unwrap()).unwrap();
+        // The skipped Bubble Sort placeholders are left out.
+        assert_eq!(csv.lines().count(), 1 + 6 * 2);
+        assert!(report.runs[0].report.results[3].skipped);
+        assert!(markdown.starts_with("## Suite results: smoke\n"));
+        assert!(markdown.contains("| people | reversed | 50 | name | Quick Sort |"));
+    }
+
+    #[test]
+    fn loads_datasets_relative_to_the_suite_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("ages.csv"), "age\n3\n1\n2\n").unwrap();
+        let path = dir.path().join("suite.toml");
+        std::fs::write(
+            &path,
+            "[[dataset]]\npath = \"ages.csv\"\n\n[[algorithm]]\nname = \"Merge Sort\"\n",
+        )
+        .unwrap();
+
+        let report = Suite::load(&path).unwrap().run(&mut |_| {}).unwrap();
+
+        assert_eq!(report.runs.len(), 1);
+        assert_eq!(report.runs[0].dataset, "ages.csv");
+        assert_eq!(report.runs[0].report.dataset.rows, 3);
+        assert_eq!(report.runs[0].distribution, Distribution::AsIs);
+    }
+
+    #[test]
+    fn limits_quick_sort_only_on_columns_with_many_repeated_keys() {
+        let column = |rows: usize, distinct: usize| -> Vec<Record> {
+            (0..rows)
+                .map(|i| vec![(i % distinct).to_string()])
+                .collect()
+        };
+        let limit = |name: &str, records: &[Record]| {
+            let (algorithm, slow) = find_algorithm(name).unwrap();
+            default_max_rows(&algorithm, slow, repeated_key_pairs(records, 0))
+        };
+
+        // An as-is boolean column.
+        assert_eq!(
+            limit("Quick Sort", &column(5000, 2)),
+            Some(BUBBLE_SORT_ROW_LIMIT)
+        );
+        assert_eq!(limit("Quick Sort", &column(5000, 5000)), None);
+        // 300 copies of each key is still cheap.
+        assert_eq!(limit("Quick Sort", &column(1500, 5)), None);
+        assert_eq!(
+            limit("Bubble Sort", &column(10, 10)),
+            Some(BUBBLE_SORT_ROW_LIMIT)
+        );
+        assert_eq!(repeated_key_pairs(&column(4, 2), 0), 4);
+    }
+
+    #[test]
+    fn rejects_invalid_suites() {
+        let parse = |text: &str| Suite::parse(text, Path::new("")).unwrap_err();
+
+        assert!(parse("trials = 2\n").contains("dataset"));
+        assert!(parse("[[dataset]]\ncolumns = [\"a\"]\n").contains("exactly one"));
+        assert!(
+            parse("[[dataset]]\npath = \"a.csv\"\n[[algorithm]]\nname = \"Bogo Sort\"\n")
+                .contains("unknown algorithm")
+        );
+        assert!(parse("[[dataset]]\npath = \"a.csv\"\ntimeout = 5\n").contains("unknown field"));
+        assert!(
+            parse("[[dataset]]\npath = \"a.txt\"\nformat = \"csv\"\nfixed_width = \"a:1\"\n")
+                .contains("does not apply")
+        );
+    }
+
+    #[test]
+    fn arranges_distributions_and_stops_trials_at_the_timeout() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let rows = |n: i64| -> Vec<Record> { (0..n).map(|i| vec![i.to_string()]).collect() };
+
+        let mut records = rows(200);
+        Distribution::Reversed.arrange(&mut records, 0, &mut rng);
+        assert_eq!(ages(&records), (0..200).rev().collect::<Vec<_>>());
+        Distribution::NearlySorted.arrange(&mut records, 0, &mut rng);
+        let out_of_place = ages(&records)
+            .iter()
+            .enumerate()
+            .filter(|&(i, &age)| i as i64 != age)
+            .count();
+        assert!(out_of_place <= 4);
+        Distribution::FewUnique.arrange(&mut records, 0, &mut rng);
+        let mut distinct = ages(&records);
+        distinct.sort();
+        distinct.dedup();
+        assert!(distinct.len() <= 8);
+
+        let suite = Suite::parse(
+            "trials = 5\ntimeout_ms = 0.0\n[[dataset]]\npath = \"a.csv\"\n",
+            Path::new(""),
+        )
+        .unwrap();
+        let spec = AlgorithmSpec::with_defaults("Std Sort");
+        let results = suite.run_trials("Std Sort".to_string(), &spec, || {
+            let mut data = rows(10);
+            move || Algorithm::Std.sort(&mut data, 0)
+        });
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].samples_ms.len(), 1);
+        assert_eq!(results[1].name, "(Std Sort timed out after 1 of 5 trials)");
+    }
+}
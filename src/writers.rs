@@ -0,0 +1,712 @@
+// Copyright (c) 2026 Neil Pandya
+
+// Non-CSV exports: JSON, JSON Lines, Markdown and HTML tables, and SQLite
+// databases, each written from the same `(headers, rows)` table as a CSV
+// export (see `io::TableWriter`).
+
+use crate::compression::Compression;
+use crate::dialect::ExportOptions;
+use crate::io::TableWriter;
+use crate::models::{CsvError, Record};
+#[cfg(feature = "sqlite")]
+use rusqlite::{Connection, DatabaseName, params_from_iter, types::Value as SqlValue};
+use serde::Serializer;
+use serde_json::{Map, Number, Value};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+/// The kinds of file an export can produce.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// In the dialect chosen by `ExportOptions`.
+    #[default]
+    Csv,
+    Json,
+    Ndjson,
+    Markdown,
+    Html,
+    Sqlite,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 6] = [
+        OutputFormat::Csv,
+        OutputFormat::Json,
+        OutputFormat::Ndjson,
+        OutputFormat::Markdown,
+        OutputFormat::Html,
+        OutputFormat::Sqlite,
+    ];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Html => "html",
+            OutputFormat::Sqlite => "sqlite",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "CSV",
+            OutputFormat::Json => "JSON (array of objects)",
+            OutputFormat::Ndjson => "JSON Lines (one object per line)",
+            OutputFormat::Markdown => "Markdown table",
+            OutputFormat::Html => "HTML table",
+            OutputFormat::Sqlite => "SQLite database",
+        }
+    }
+
+    /// File extension without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "jsonl",
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+            OutputFormat::Sqlite => "sqlite",
+        }
+    }
+
+    /// Guesses from the extension, looking through a compression suffix
+    /// (`sorted.json.gz` is JSON); anything unrecognised is CSV.
+    pub fn from_path(path: &Path) -> Self {
+        let path = match Compression::from_path(path) {
+            Compression::None => path.to_path_buf(),
+            _ => path.with_extension(""),
+        };
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => OutputFormat::Json,
+            "jsonl" | "ndjson" => OutputFormat::Ndjson,
+            "md" | "markdown" => OutputFormat::Markdown,
+            "html" | "htm" => OutputFormat::Html,
+            "sqlite" | "sqlite3" | "db" => OutputFormat::Sqlite,
+            _ => OutputFormat::Csv,
+        }
+    }
+
+    /// The writer for this format. CSV is written by `options` itself; the
+    /// text formats reuse its compression and are always UTF-8.
+    pub fn writer(&self, options: &ExportOptions) -> Box<dyn TableWriter> {
+        let compression = options.compression;
+        match self {
+            OutputFormat::Csv => Box::new(*options),
+            OutputFormat::Json => Box::new(JsonWriter { compression }),
+            OutputFormat::Ndjson => Box::new(NdjsonWriter { compression }),
+            OutputFormat::Markdown => Box::new(MarkdownWriter { compression }),
+            OutputFormat::Html => Box::new(HtmlWriter { compression }),
+            OutputFormat::Sqlite => Box::new(SqliteWriter {
+                table: SQLITE_TABLE.to_string(),
+            }),
+        }
+    }
+}
+
+/// Name of the table `OutputFormat::Sqlite` exports write.
+pub const SQLITE_TABLE: &str = "data";
+
+/// The narrowest type every non-empty cell of a column fits; empty cells
+/// count as missing values rather than text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Real,
+    Text,
+}
+
+impl ColumnType {
+    #[cfg(feature = "sqlite")]
+    fn sql_name(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "INTEGER",
+            ColumnType::Real => "REAL",
+            ColumnType::Text => "TEXT",
+        }
+    }
+
+    /// A cell is only numeric if the number prints back as the same text, so
+    /// `007`, `+5` or a 20-digit ID stay text instead of becoming 7, 5 or
+    /// `1.2345678901234567e19`.
+    fn of(cell: &str) -> Self {
+        if exact_integer(cell).is_some() {
+            ColumnType::Integer
+        } else if exact_real(cell).is_some() {
+            ColumnType::Real
+        } else {
+            ColumnType::Text
+        }
+    }
+
+    /// The cell as a JSON value: numbers for numeric columns, `null` for
+    /// their empty cells, and strings otherwise.
+    fn json(&self, cell: &str) -> Value {
+        match self {
+            ColumnType::Text => Value::String(cell.to_string()),
+            _ if cell.is_empty() => Value::Null,
+            _ => exact_integer(cell)
+                .map(Number::from)
+                .or_else(|| exact_real(cell))
+                .map_or(Value::Null, Value::Number),
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn sql(&self, cell: &str) -> SqlValue {
+        match self {
+            ColumnType::Text => SqlValue::Text(cell.to_string()),
+            _ if cell.is_empty() => SqlValue::Null,
+            ColumnType::Integer => exact_integer(cell).map_or(SqlValue::Null, SqlValue::Integer),
+            ColumnType::Real => cell.parse().map_or(SqlValue::Null, SqlValue::Real),
+        }
+    }
+}
+
+/// `cell` as an integer, if it prints back as exactly the same text.
+fn exact_integer(cell: &str) -> Option<i64> {
+    cell.parse::<i64>().ok().filter(|n| n.to_string() == cell)
+}
+
+/// `cell` as a finite JSON number, if it prints back as exactly the same text.
+fn exact_real(cell: &str) -> Option<Number> {
+    cell.parse::<f64>()
+        .ok()
+        .and_then(Number::from_f64)
+        .filter(|n| n.to_string() == cell)
+}
+
+/// Infers one `ColumnType` per header. A column with no values at all is text.
+pub fn infer_column_types(width: usize, records: &[Record]) -> Vec<ColumnType> {
+    (0..width)
+        .map(|column| {
+            let mut cells = records
+                .iter()
+                .filter_map(|record| record.get(column))
+                .filter(|cell| !cell.is_empty())
+                .peekable();
+            if cells.peek().is_none() {
+                return ColumnType::Text;
+            }
+            cells.fold(ColumnType::Integer, |ty, cell| {
+                match (ty, ColumnType::of(cell)) {
+                    (ColumnType::Text, _) | (_, ColumnType::Text) => ColumnType::Text,
+                    (ColumnType::Real, _) | (_, ColumnType::Real) => ColumnType::Real,
+                    _ => ColumnType::Integer,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Each row as a JSON object keyed by header, values typed per column.
+/// Repeated headers are renamed as in `unique_column_names`, so no column
+/// overwrites another.
+fn json_objects<'a>(
+    headers: &'a [String],
+    records: &'a [Record],
+) -> impl Iterator<Item = Map<String, Value>> + 'a {
+    let types = infer_column_types(headers.len(), records);
+    let names = unique_column_names(headers);
+    records.iter().map(move |record| {
+        names
+            .iter()
+            .zip(&types)
+            .enumerate()
+            .map(|(i, (header, ty))| {
+                let cell = record.get(i).map_or("", String::as_str);
+                (header.clone(), ty.json(cell))
+            })
+            .collect()
+    })
+}
+
+/// Fails on a row with more cells than there are headers, which these formats
+/// have no column for.
+fn check_width(headers: &[String], records: &[Record]) -> Result<(), CsvError> {
+    match records
+        .iter()
+        .position(|record| record.len() > headers.len())
+    {
+        Some(row) => Err(CsvError::Export {
+            path: None,
+            source: format!(
+                "row {} has {} cells but there are only {} columns",
+                row + 1,
+                records[row].len(),
+                headers.len()
+            )
+            .into(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn json_error(e: serde_json::Error) -> CsvError {
+    CsvError::from(std::io::Error::from(e))
+}
+
+/// Runs `write` against `out` through `compression`, finishing the stream.
+fn write_compressed(
+    out: &mut dyn Write,
+    compression: Compression,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), CsvError>,
+) -> Result<(), CsvError> {
+    let mut compressed = compression.compress_writer(out)?;
+    write(&mut compressed)?;
+    compressed.finish()?;
+    Ok(())
+}
+
+/// A JSON array of objects, one per row.
+pub struct JsonWriter {
+    pub compression: Compression,
+}
+
+impl TableWriter for JsonWriter {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError> {
+        check_width(headers, records)?;
+        write_compressed(out, self.compression, |out| {
+            serde_json::Serializer::pretty(&mut *out)
+                .collect_seq(json_objects(headers, records))
+                .map_err(json_error)?;
+            Ok(out.write_all(b"\n")?)
+        })
+    }
+}
+
+/// JSON Lines: one compact object per row.
+pub struct NdjsonWriter {
+    pub compression: Compression,
+}
+
+impl TableWriter for NdjsonWriter {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError> {
+        check_width(headers, records)?;
+        write_compressed(out, self.compression, |out| {
+            for object in json_objects(headers, records) {
+                serde_json::to_writer(&mut *out, &object).map_err(json_error)?;
+                out.write_all(b"\n")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// A GitHub-flavoured Markdown table; numeric columns are right-aligned.
+pub struct MarkdownWriter {
+    pub compression: Compression,
+}
+
+impl TableWriter for MarkdownWriter {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError> {
+        check_width(headers, records)?;
+        let escape = |cell: &str| {
+            cell.replace('\\', "\\\\")
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace('\n', "<br>")
+        };
+        let row =
+            |out: &mut dyn Write, cells: Vec<String>| writeln!(out, "| {} |", cells.join(" | "));
+
+        write_compressed(out, self.compression, |out| {
+            row(out, headers.iter().map(|h| escape(h)).collect())?;
+            let alignments = infer_column_types(headers.len(), records)
+                .into_iter()
+                .map(|ty| match ty {
+                    ColumnType::Text => "---".to_string(),
+                    _ => "---:".to_string(),
+                });
+            row(out, alignments.collect())?;
+            for record in records {
+                let cells = (0..headers.len())
+                    .map(|i| escape(record.get(i).map_or("", String::as_str)))
+                    .collect();
+                row(out, cells)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// A standalone `<table>` element, for pasting into reports.
+pub struct HtmlWriter {
+    pub compression: Compression,
+}
+
+impl TableWriter for HtmlWriter {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError> {
+        check_width(headers, records)?;
+        let escape = |cell: &str| {
+            cell.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        };
+
+        write_compressed(out, self.compression, |out| {
+            write!(out, "<table>\n  <thead>\n    <tr>")?;
+            for header in headers {
+                write!(out, "<th>{}</th>", escape(header))?;
+            }
+            write!(out, "</tr>\n  </thead>\n  <tbody>\n")?;
+            for record in records {
+                write!(out, "    <tr>")?;
+                for i in 0..headers.len() {
+                    let cell = record.get(i).map_or("", String::as_str);
+                    write!(out, "<td>{}</td>", escape(cell))?;
+                }
+                writeln!(out, "</tr>")?;
+            }
+            write!(out, "  </tbody>\n</table>\n")?;
+            Ok(())
+        })
+    }
+}
+
+/// A SQLite database holding one table, with `INTEGER`, `REAL` or `TEXT`
+/// columns inferred from the data; rows keep their sorted order as rowids.
+/// Needs the `sqlite` feature.
+pub struct SqliteWriter {
+    pub table: String,
+}
+
+#[cfg(not(feature = "sqlite"))]
+impl TableWriter for SqliteWriter {
+    fn write_table(
+        &self,
+        _out: &mut dyn Write,
+        _headers: &[String],
+        _records: &[Record],
+    ) -> Result<(), CsvError> {
+        Err(crate::models::unsupported("SQLite", "sqlite").into())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl TableWriter for SqliteWriter {
+    fn write_table(
+        &self,
+        out: &mut dyn Write,
+        headers: &[String],
+        records: &[Record],
+    ) -> Result<(), CsvError> {
+        let sql_error = |e: rusqlite::Error| CsvError::Export {
+            path: None,
+            source: Box::new(e),
+        };
+        if headers.is_empty() {
+            return Err(CsvError::Export {
+                path: None,
+                source: "a SQLite table needs at least one column".into(),
+            });
+        }
+        check_width(headers, records)?;
+        let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+
+        let types = infer_column_types(headers.len(), records);
+        let columns: Vec<String> = unique_column_names(headers)
+            .iter()
+            .zip(&types)
+            .map(|(header, ty)| format!("{} {}", quote(header), ty.sql_name()))
+            .collect();
+        let placeholders = vec!["?"; headers.len()].join(", ");
+
+        let mut conn = Connection::open_in_memory().map_err(sql_error)?;
+        conn.execute(
+            &format!(
+                "CREATE TABLE {} ({})",
+                quote(&self.table),
+                columns.join(", ")
+            ),
+            [],
+        )
+        .map_err(sql_error)?;
+        let tx = conn.transaction().map_err(sql_error)?;
+        {
+            let mut insert = tx
+                .prepare(&format!(
+                    "INSERT INTO {} VALUES ({})",
+                    quote(&self.table),
+                    placeholders
+                ))
+                .map_err(sql_error)?;
+            for record in records {
+                let values = types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| ty.sql(record.get(i).map_or("", String::as_str)));
+                insert
+                    .execute(params_from_iter(values))
+                    .map_err(sql_error)?;
+            }
+        }
+        tx.commit().map_err(sql_error)?;
+
+        let bytes = conn.serialize(DatabaseName::Main).map_err(sql_error)?;
+        Ok(out.write_all(&bytes)?)
+    }
+}
+
+/// The headers as column names for formats keyed by name: a repeated name
+/// (compared case-insensitively, as SQLite does) gets a `_2`, `_3`, … suffix.
+fn unique_column_names(headers: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    headers
+        .iter()
+        .map(|header| {
+            let mut name = header.clone();
+            let mut copy = 1;
+            while !seen.insert(name.to_ascii_lowercase()) {
+                copy += 1;
+                name = format!("{}_{}", header, copy);
+            }
+            name
+        })
+        .collect()
+}
+
+// ----------  TESTS  -------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "sqlite")]
+    use tempfile::TempDir;
+
+    fn table() -> (Vec<String>, Vec<Record>) {
+        let headers = vec!["name".to_string(), "age".to_string(), "score".to_string()];
+        let records = vec![
+            vec!["Bob".to_string(), "25".to_string(), "1.5".to_string()],
+            vec!["A|<b>".to_string(), "".to_string(), "2".to_string()],
+        ];
+        (headers, records)
+    }
+
+    #[test]
+    fn infers_the_narrowest_type_per_column() {
+        let (headers, records) = table();
+
+        assert_eq!(
+            infer_column_types(headers.len(), &records),
+            vec![ColumnType::Text, ColumnType::Integer, ColumnType::Real]
+        );
+    }
+
+    #[test]
+    fn keeps_cells_as_text_unless_they_print_back_unchanged() {
+        let records = vec![
+            vec![
+                "007".to_string(),
+                "12345678901234567890".to_string(),
+                "1.50".to_string(),
+            ],
+            vec!["7".to_string(), "1".to_string(), "1.5".to_string()],
+        ];
+
+        assert_eq!(infer_column_types(3, &records), vec![ColumnType::Text; 3]);
+        assert_eq!(ColumnType::of("-12"), ColumnType::Integer);
+        assert_eq!(ColumnType::of("0.25"), ColumnType::Real);
+    }
+
+    #[test]
+    fn writes_typed_json_and_ndjson() {
+        let (headers, records) = table();
+        let compression = Compression::None;
+
+        let (mut json, mut ndjson) = (Vec::new(), Vec::new());
+        JsonWriter { compression }
+            .write_table(&mut json, &headers, &records)
+            .unwrap();
+        NdjsonWriter { compression }
+            .write_table(&mut ndjson, &headers, &records)
+            .unwrap();
+
+        let rows: Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(rows[0]["age"], 25);
+        assert_eq!(rows[1]["age"], Value::Null);
+        assert_eq!(rows[1]["score"], 2.0);
+        assert_eq!(
+            String::from_utf8(ndjson).unwrap(),
+            "{\"name\":\"Bob\",\"age\":25,\"score\":1.5}\n{\"name\":\"A|<b>\",\"age\":null,\"score\":2}\n"
+        );
+    }
+
+    #[test]
+    fn escapes_markdown_and_html_cells() {
+        let (headers, records) = table();
+        let compression = Compression::None;
+
+        let (mut markdown, mut html) = (Vec::new(), Vec::new());
+        MarkdownWriter { compression }
+            .write_table(&mut markdown, &headers, &records)
+            .unwrap();
+        HtmlWriter { compression }
+            .write_table(&mut html, &headers, &records)
+            .unwrap();
+
+        let markdown = String::from_utf8(markdown).unwrap();
+        assert!(markdown.starts_with("| name | age | score |\n| --- | ---: | ---: |\n"));
+        assert!(markdown.contains("| A\\|<b> |  | 2 |"));
+        assert!(
+            String::from_utf8(html)
+                .unwrap()
+                .contains("<td>A|&lt;b&gt;</td><td></td>")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn writes_a_sqlite_table_with_inferred_column_types() {
+        let (headers, records) = table();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("sorted.sqlite");
+
+        let mut bytes = Vec::new();
+        OutputFormat::Sqlite
+            .writer(&ExportOptions::default())
+            .write_table(&mut bytes, &headers, &records)
+            .unwrap();
+        std::fs::write(&path, bytes).unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+        let rows: Vec<(String, Option<i64>, f64)> = conn
+            .prepare("SELECT name, age, score FROM data ORDER BY rowid")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ("Bob".to_string(), Some(25), 1.5),
+                ("A|<b>".to_string(), None, 2.0)
+            ]
+        );
+        let age_type: String = conn
+            .query_row(
+                "SELECT type FROM pragma_table_info('data') WHERE name = 'age'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(age_type, "INTEGER");
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn renames_repeated_sqlite_columns() {
+        let headers = vec!["a".to_string(), "A".to_string(), "a".to_string()];
+        let mut bytes = Vec::new();
+
+        SqliteWriter {
+            table: SQLITE_TABLE.to_string(),
+        }
+        .write_table(
+            &mut bytes,
+            &headers,
+            &[vec!["1".into(), "2".into(), "3".into()]],
+        )
+        .unwrap();
+
+        assert_eq!(unique_column_names(&headers), vec!["a", "A_2", "a_3"]);
+    }
+
+    #[test]
+    fn keeps_every_repeated_json_column() {
+        let headers = vec!["a".to_string(), "a".to_string()];
+        let mut ndjson = Vec::new();
+
+        NdjsonWriter {
+            compression: Compression::None,
+        }
+        .write_table(&mut ndjson, &headers, &[vec!["x".into(), "y".into()]])
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(ndjson).unwrap(),
+            "{\"a\":\"x\",\"a_2\":\"y\"}\n"
+        );
+    }
+
+    #[test]
+    fn rejects_rows_wider_than_the_headers() {
+        let (headers, mut records) = table();
+        records[1].push("extra".to_string());
+
+        for format in OutputFormat::ALL {
+            if format == OutputFormat::Csv
+                || (format == OutputFormat::Sqlite && cfg!(not(feature = "sqlite")))
+            {
+                continue;
+            }
+            let result = format.writer(&ExportOptions::default()).write_table(
+                &mut Vec::new(),
+                &headers,
+                &records,
+            );
+            match result {
+                Err(CsvError::Export { source, .. }) => assert_eq!(
+                    source.to_string(),
+                    "row 2 has 4 cells but there are only 3 columns",
+                    "{}",
+                    format.name()
+                ),
+                other => panic!("Expected Export error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn rejects_a_sqlite_table_without_columns() {
+        let result = SqliteWriter {
+            table: SQLITE_TABLE.to_string(),
+        }
+        .write_table(&mut Vec::new(), &[], &[]);
+
+        assert!(matches!(result, Err(CsvError::Export { .. })));
+    }
+
+    #[test]
+    fn picks_the_format_from_the_extension() {
+        assert_eq!(
+            OutputFormat::from_path(Path::new("a.json.gz")),
+            OutputFormat::Json
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("a.db")),
+            OutputFormat::Sqlite
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("a.tsv")),
+            OutputFormat::Csv
+        );
+    }
+}